@@ -0,0 +1,185 @@
+use crate::{Field, LorentzVector, TransverseVector};
+use num::Float;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fmt::Display;
+use std::num::FpCategory;
+
+/// Values whose floating-point components can be inspected by an `AuditLog`.
+pub trait AuditComponents<T: Float> {
+    fn for_each_component<F: FnMut(T)>(&self, f: F);
+}
+
+impl AuditComponents<f32> for f32 {
+    #[inline]
+    fn for_each_component<F: FnMut(f32)>(&self, mut f: F) {
+        f(*self)
+    }
+}
+
+impl AuditComponents<f64> for f64 {
+    #[inline]
+    fn for_each_component<F: FnMut(f64)>(&self, mut f: F) {
+        f(*self)
+    }
+}
+
+impl<T: Float + Field> AuditComponents<T> for LorentzVector<T> {
+    #[inline]
+    fn for_each_component<F: FnMut(T)>(&self, mut f: F) {
+        f(self.t);
+        f(self.x);
+        f(self.y);
+        f(self.z);
+    }
+}
+
+impl<T: Float + Field> AuditComponents<T> for TransverseVector<T> {
+    #[inline]
+    fn for_each_component<F: FnMut(T)>(&self, mut f: F) {
+        f(self.x);
+        f(self.y);
+    }
+}
+
+impl<T: Float, A: AuditComponents<T>> AuditComponents<T> for Option<A> {
+    #[inline]
+    fn for_each_component<F: FnMut(T)>(&self, f: F) {
+        if let Some(a) = self {
+            a.for_each_component(f);
+        }
+    }
+}
+
+/// The number of calls of a single function and of the problems found in their results.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct AuditCounts {
+    pub calls: usize,
+    /// Calls with a subnormal component in the result.
+    pub subnormal: usize,
+    /// Calls that produced a NaN from inputs without NaNs.
+    pub nan: usize,
+    /// Calls whose result lost more binary digits of magnitude than the threshold
+    /// with respect to the largest input component.
+    pub cancellations: usize,
+}
+
+/// A log of the numerical health of instrumented calls, to locate the operations
+/// that destroy precision. Calls are wrapped with `call`, which inspects the result
+/// with respect to the inputs.
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    cancellation_bits: i32,
+    counts: BTreeMap<&'static str, AuditCounts>,
+}
+
+impl Default for AuditLog {
+    fn default() -> AuditLog {
+        AuditLog::new()
+    }
+}
+
+/// The binary exponent of the magnitude of a non-zero finite number.
+#[inline]
+fn exponent<T: Float>(x: T) -> Option<i32> {
+    if x == T::zero() || !x.is_finite() {
+        return None;
+    }
+    let (mantissa, exp, _) = x.integer_decode();
+    Some(i32::from(exp) + 64 - mantissa.leading_zeros() as i32)
+}
+
+impl AuditLog {
+    /// Create a log that reports a cancellation when at least 20 bits are lost.
+    pub fn new() -> AuditLog {
+        AuditLog::with_cancellation_threshold(20)
+    }
+
+    /// Create a log that reports a cancellation when the magnitude of a non-zero result
+    /// component is at least `bits` binary digits below the largest input component,
+    /// or when a result component is exactly zero for non-zero inputs.
+    pub fn with_cancellation_threshold(bits: u32) -> AuditLog {
+        AuditLog {
+            cancellation_bits: bits as i32,
+            counts: BTreeMap::new(),
+        }
+    }
+
+    /// Evaluate `f`, which computes a result from `inputs`, and record the problems
+    /// of the result under `name`.
+    pub fn call<T: Float + Field, R: AuditComponents<T>, F: FnOnce() -> R>(
+        &mut self,
+        name: &'static str,
+        inputs: &[LorentzVector<T>],
+        f: F,
+    ) -> R {
+        let mut input_nan = false;
+        let mut input_exponent = None;
+        for p in inputs {
+            p.for_each_component(|c| {
+                input_nan |= c.is_nan();
+                input_exponent = input_exponent.max(exponent(c));
+            });
+        }
+
+        let result = f();
+
+        let (mut subnormal, mut nan, mut cancellation) = (false, false, false);
+        result.for_each_component(|c| {
+            subnormal |= c.classify() == FpCategory::Subnormal;
+            nan |= c.is_nan() && !input_nan;
+            if let Some(e_in) = input_exponent {
+                cancellation |= match exponent(c) {
+                    Some(e) => e_in - e >= self.cancellation_bits,
+                    None => c == T::zero(),
+                };
+            }
+        });
+
+        let counts = self.counts.entry(name).or_default();
+        counts.calls += 1;
+        counts.subnormal += subnormal as usize;
+        counts.nan += nan as usize;
+        counts.cancellations += cancellation as usize;
+        result
+    }
+
+    /// Get the counts recorded under `name`.
+    #[inline]
+    pub fn counts(&self, name: &str) -> Option<&AuditCounts> {
+        self.counts.get(name)
+    }
+
+    /// Iterate over the recorded functions in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &AuditCounts)> {
+        self.counts.iter().map(|(n, c)| (*n, c))
+    }
+
+    /// Combine with the counts of another log.
+    pub fn merge(&mut self, other: &AuditLog) {
+        for (name, c) in other.iter() {
+            let counts = self.counts.entry(name).or_default();
+            counts.calls += c.calls;
+            counts.subnormal += c.subnormal;
+            counts.nan += c.nan;
+            counts.cancellations += c.cancellations;
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.clear();
+    }
+}
+
+impl Display for AuditLog {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (name, c) in self.iter() {
+            writeln!(
+                f,
+                "{}: calls={}, subnormal={}, nan={}, cancellations={}",
+                name, c.calls, c.subnormal, c.nan, c.cancellations
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{Field, LorentzVector};
+
+/// A cache of the Minkowski products `p_i.p_j` of a set of momenta.
+/// Products are computed on first access and invalidated when the momenta change.
+#[derive(Debug, Clone)]
+pub struct InvariantCache<T: Field> {
+    momenta: Vec<LorentzVector<T>>,
+    products: Vec<Option<T>>,
+}
+
+impl<T: Field> InvariantCache<T> {
+    pub fn new(momenta: Vec<LorentzVector<T>>) -> InvariantCache<T> {
+        let n = momenta.len();
+        InvariantCache {
+            momenta,
+            products: vec![None; n * (n + 1) / 2],
+        }
+    }
+
+    #[inline]
+    fn index(i: usize, j: usize) -> usize {
+        let (i, j) = if i >= j { (i, j) } else { (j, i) };
+        i * (i + 1) / 2 + j
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.momenta.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.momenta.is_empty()
+    }
+
+    #[inline]
+    pub fn momenta(&self) -> &[LorentzVector<T>] {
+        &self.momenta
+    }
+
+    /// Get the product `p_i.p_j`, computing it if it is not cached.
+    #[inline]
+    pub fn dot(&mut self, i: usize, j: usize) -> T {
+        let index = InvariantCache::<T>::index(i, j);
+        match self.products[index] {
+            Some(d) => d,
+            None => {
+                let d = self.momenta[i].dot(&self.momenta[j]);
+                self.products[index] = Some(d);
+                d
+            }
+        }
+    }
+
+    /// Get the square `p_i^2`.
+    #[inline]
+    pub fn square(&mut self, i: usize) -> T {
+        self.dot(i, i)
+    }
+
+    /// Replace momentum `i`, invalidating all products that involve it.
+    pub fn set_momentum(&mut self, i: usize, p: LorentzVector<T>) {
+        self.momenta[i] = p;
+        for j in 0..self.momenta.len() {
+            self.products[InvariantCache::<T>::index(i, j)] = None;
+        }
+    }
+
+    /// Replace all momenta, for example for a new phase-space point.
+    /// The storage is reused if the number of momenta does not change.
+    pub fn set_momenta(&mut self, momenta: &[LorentzVector<T>]) {
+        self.momenta.clear();
+        self.momenta.extend_from_slice(momenta);
+        let n = momenta.len();
+        self.products.clear();
+        self.products.resize(n * (n + 1) / 2, None);
+    }
+
+    /// Invalidate all cached products.
+    pub fn invalidate(&mut self) {
+        for p in &mut self.products {
+            *p = None;
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Field, LorentzVector};
+use num::Float;
+
+/// The mass of the proton in GeV.
+pub const PROTON_MASS: f64 = 0.938_272_088_16;
+/// The mass of the electron in GeV.
+pub const ELECTRON_MASS: f64 = 0.000_510_998_950;
+
+/// A configuration of two colliding beams, with all energies in GeV. The first beam
+/// moves along the positive z-axis and the second beam along the negative z-axis. For
+/// a non-zero crossing angle, both beams are tilted by half the angle towards the
+/// positive x-axis, such that the colliding system moves along the x-axis.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Collider<T: Float> {
+    pub energy1: T,
+    pub mass1: T,
+    /// The energy of the second beam, which is equal to its mass for a fixed target.
+    pub energy2: T,
+    pub mass2: T,
+    /// The full crossing angle of the beams in the x-z plane.
+    pub crossing_angle: T,
+}
+
+impl<T: Float + Field> Collider<T> {
+    /// Create a head-on collider with beam energies `energy1` and `energy2`.
+    pub fn new(energy1: T, mass1: T, energy2: T, mass2: T) -> Collider<T> {
+        Collider {
+            energy1,
+            mass1,
+            energy2,
+            mass2,
+            crossing_angle: T::zero(),
+        }
+    }
+
+    /// Create a symmetric collider of particles with mass `mass` at center-of-mass
+    /// energy `sqrt_s`.
+    pub fn symmetric(sqrt_s: T, mass: T) -> Collider<T> {
+        let e = sqrt_s / T::from(2.).unwrap();
+        Collider::new(e, mass, e, mass)
+    }
+
+    /// The LHC in Run 2, with 6.5 TeV proton beams.
+    pub fn lhc_13_tev() -> Collider<T> {
+        Collider::symmetric(T::from(13_000.).unwrap(), T::from(PROTON_MASS).unwrap())
+    }
+
+    /// The LHC in Run 3, with 6.8 TeV proton beams.
+    pub fn lhc_13_6_tev() -> Collider<T> {
+        Collider::symmetric(T::from(13_600.).unwrap(), T::from(PROTON_MASS).unwrap())
+    }
+
+    /// FCC-ee at center-of-mass energy `sqrt_s`, including its crossing angle of 30 mrad.
+    /// The electron is the first beam.
+    pub fn fcc_ee(sqrt_s: T) -> Collider<T> {
+        Collider::symmetric(sqrt_s, T::from(ELECTRON_MASS).unwrap())
+            .with_crossing_angle(T::from(0.030).unwrap())
+    }
+
+    /// FCC-ee at the Z pole.
+    pub fn fcc_ee_z() -> Collider<T> {
+        Collider::fcc_ee(T::from(91.2).unwrap())
+    }
+
+    /// FCC-ee at the WW threshold.
+    pub fn fcc_ee_ww() -> Collider<T> {
+        Collider::fcc_ee(T::from(160.).unwrap())
+    }
+
+    /// FCC-ee at the maximum of the ZH cross section.
+    pub fn fcc_ee_zh() -> Collider<T> {
+        Collider::fcc_ee(T::from(240.).unwrap())
+    }
+
+    /// FCC-ee above the top-pair threshold.
+    pub fn fcc_ee_top() -> Collider<T> {
+        Collider::fcc_ee(T::from(365.).unwrap())
+    }
+
+    /// HERA in its final configuration, with a 920 GeV proton beam along the positive
+    /// z-axis and a 27.5 GeV electron or positron beam along the negative z-axis.
+    pub fn hera() -> Collider<T> {
+        Collider::new(
+            T::from(920.).unwrap(),
+            T::from(PROTON_MASS).unwrap(),
+            T::from(27.5).unwrap(),
+            T::from(ELECTRON_MASS).unwrap(),
+        )
+    }
+
+    /// A beam with energy `energy` and mass `mass` on a target at rest with mass
+    /// `target_mass`.
+    pub fn fixed_target(energy: T, mass: T, target_mass: T) -> Collider<T> {
+        Collider::new(energy, mass, target_mass, target_mass)
+    }
+
+    /// Set the full crossing angle of the beams.
+    pub fn with_crossing_angle(mut self, crossing_angle: T) -> Collider<T> {
+        self.crossing_angle = crossing_angle;
+        self
+    }
+
+    /// Express all energies in units of `unit` GeV, for example `1000` for TeV.
+    pub fn in_units(&self, unit: T) -> Collider<T> {
+        Collider {
+            energy1: self.energy1 / unit,
+            mass1: self.mass1 / unit,
+            energy2: self.energy2 / unit,
+            mass2: self.mass2 / unit,
+            crossing_angle: self.crossing_angle,
+        }
+    }
+
+    /// The momenta of the two beams.
+    pub fn beams(&self) -> (LorentzVector<T>, LorentzVector<T>) {
+        let (sin, cos) = (self.crossing_angle / T::from(2.).unwrap()).sin_cos();
+        let p1 = (self.energy1 * self.energy1 - self.mass1 * self.mass1)
+            .max(T::zero())
+            .sqrt();
+        let p2 = (self.energy2 * self.energy2 - self.mass2 * self.mass2)
+            .max(T::zero())
+            .sqrt();
+        (
+            LorentzVector::from_args(self.energy1, p1 * sin, T::zero(), p1 * cos),
+            LorentzVector::from_args(self.energy2, p2 * sin, T::zero(), -p2 * cos),
+        )
+    }
+
+    /// The center-of-mass energy of the beams.
+    pub fn sqrt_s(&self) -> T {
+        let (p1, p2) = self.beams();
+        (p1 + p2).m()
+    }
+
+    /// The momenta of partons that carry the fractions `x1` and `x2` of the momenta of
+    /// the beams.
+    pub fn partons(&self, x1: T, x2: T) -> (LorentzVector<T>, LorentzVector<T>) {
+        let (p1, p2) = self.beams();
+        (p1 * x1, p2 * x2)
+    }
+
+    /// The velocity of the center-of-mass frame of the partons with momentum fractions
+    /// `x1` and `x2` in the lab frame. Boosting with this velocity, for example with
+    /// `LorentzVector::boost_slice()`, brings momenta from the partonic center-of-mass
+    /// frame to the lab frame.
+    pub fn parton_boost_vector(&self, x1: T, x2: T) -> LorentzVector<T> {
+        let (k1, k2) = self.partons(x1, x2);
+        (k1 + k2).beta_vector()
+    }
+
+    /// Boost momenta in place from the center-of-mass frame of the partons with
+    /// momentum fractions `x1` and `x2` to the lab frame.
+    pub fn boost_from_parton_frame(&self, momenta: &mut [LorentzVector<T>], x1: T, x2: T) {
+        LorentzVector::boost_slice(momenta, &self.parton_boost_vector(x1, x2));
+    }
+}
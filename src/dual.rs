@@ -0,0 +1,186 @@
+use crate::{Error, LorentzVector, RealNumberLike};
+use dual_num::{Allocator, DefaultAllocator, Dim, DimName, DualN, Owned};
+use num::Signed;
+
+/// Compute the gradient `dO/dp_i^mu` of the observable `observable` with respect to all
+/// components of the momenta of `event`. Every component is seeded with its own dual
+/// direction, in the order `4 i + mu`, so the duals need at least `4 n + 1` components
+/// for `n` momenta; otherwise `Error::DualDimension` is returned.
+pub fn gradient_of<T, U, F>(
+    observable: F,
+    event: &[LorentzVector<T>],
+) -> Result<Vec<LorentzVector<T>>, Error>
+where
+    T: RealNumberLike + Signed + 'static,
+    U: Dim + DimName,
+    DefaultAllocator: Allocator<T, U>,
+    Owned<T, U>: Copy,
+    F: Fn(&[LorentzVector<DualN<T, U>>]) -> DualN<T, U>,
+{
+    let n = 4 * event.len();
+    if U::dim() < n + 1 {
+        return Err(Error::DualDimension {
+            required: n + 1,
+            found: U::dim(),
+        });
+    }
+
+    let seeded: Vec<LorentzVector<DualN<T, U>>> = event
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            p.map_with_index(|mu, c| {
+                let mut d = DualN::from_real(c);
+                d[4 * i + mu + 1] = T::one();
+                d
+            })
+        })
+        .collect();
+
+    let r = observable(&seeded);
+    Ok((0..event.len())
+        .map(|i| LorentzVector::from_args(r[4 * i + 1], r[4 * i + 2], r[4 * i + 3], r[4 * i + 4]))
+        .collect())
+}
+
+/// Compute the Hessian `d^2 O / (dp_i^mu dp_j^nu)` of the observable `observable` with
+/// respect to all components of the momenta of `event`, using nested duals. The rows and
+/// columns are in the order `4 i + mu` of `gradient_of()`, and the duals need at least
+/// `4 n + 1` components for `n` momenta; otherwise `Error::DualDimension` is returned.
+pub fn hessian_of<T, U, F>(observable: F, event: &[LorentzVector<T>]) -> Result<Vec<Vec<T>>, Error>
+where
+    T: RealNumberLike + dual_num::FloatConst + Signed + 'static,
+    DualN<T, U>: Signed,
+    U: Dim + DimName,
+    DefaultAllocator: Allocator<T, U> + Allocator<DualN<T, U>, U>,
+    Owned<T, U>: Copy,
+    Owned<DualN<T, U>, U>: Copy,
+    F: Fn(&[LorentzVector<DualN<DualN<T, U>, U>>]) -> DualN<DualN<T, U>, U>,
+{
+    let n = 4 * event.len();
+    if U::dim() < n + 1 {
+        return Err(Error::DualDimension {
+            required: n + 1,
+            found: U::dim(),
+        });
+    }
+
+    // the inner duals carry the first derivatives and the outer duals the derivatives
+    // of those, so that both levels are seeded in the same direction
+    let seeded: Vec<LorentzVector<DualN<DualN<T, U>, U>>> = event
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            p.map_with_index(|mu, c| {
+                let mut inner = DualN::<T, U>::from_real(c);
+                inner[4 * i + mu + 1] = T::one();
+                let mut d = DualN::from_real(inner);
+                d[4 * i + mu + 1] = DualN::from_real(T::one());
+                d
+            })
+        })
+        .collect();
+
+    let r = observable(&seeded);
+    Ok((0..n)
+        .map(|k| (0..n).map(|l| r[k + 1][l + 1]).collect())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dual_num::{U5, U9};
+
+    fn components(p: &LorentzVector<f64>) -> (f64, f64, f64, f64) {
+        (p.t, p.x, p.y, p.z)
+    }
+
+    #[test]
+    fn gradient_of_square() {
+        let p = LorentzVector::from_args(4., 1., 2., 3.);
+        let g = gradient_of::<f64, U5, _>(|q| q[0].square(), &[p]).unwrap();
+        assert_eq!(g.len(), 1);
+        assert_eq!(components(&g[0]), (8., -2., -4., -6.));
+    }
+
+    #[test]
+    fn gradient_of_pair_square() {
+        let p1 = LorentzVector::from_args(4., 1., 2., 3.);
+        let p2 = LorentzVector::from_args(5., -1., 0., 2.);
+        let g = gradient_of::<f64, U9, _>(|q| (q[0] + q[1]).square(), &[p1, p2]).unwrap();
+        assert_eq!(g.len(), 2);
+        assert_eq!(components(&g[0]), (18., 0., -4., -10.));
+        assert_eq!(components(&g[1]), (18., 0., -4., -10.));
+
+        assert_eq!(
+            gradient_of::<f64, U5, _>(|q| (q[0] + q[1]).square(), &[p1, p2]).unwrap_err(),
+            Error::DualDimension {
+                required: 9,
+                found: 5
+            }
+        );
+    }
+
+    /// The metric `g_{mu nu}` as a row-major 4 x 4 matrix.
+    fn metric() -> [[f64; 4]; 4] {
+        [
+            [1., 0., 0., 0.],
+            [0., -1., 0., 0.],
+            [0., 0., -1., 0.],
+            [0., 0., 0., -1.],
+        ]
+    }
+
+    #[test]
+    fn hessian_of_square() {
+        let p = LorentzVector::from_args(4., 1., 2., 3.);
+        let h = hessian_of::<f64, U5, _>(|q| q[0].square(), &[p]).unwrap();
+        assert_eq!(h.len(), 4);
+        for (mu, row) in h.iter().enumerate() {
+            let expected: Vec<f64> = metric()[mu].iter().map(|g| 2. * g).collect();
+            assert_eq!(row, &expected);
+        }
+    }
+
+    #[test]
+    fn hessian_of_dot() {
+        let p1 = LorentzVector::from_args(4., 1., 2., 3.);
+        let p2 = LorentzVector::from_args(5., -1., 0., 2.);
+        let h = hessian_of::<f64, U9, _>(|q| q[0].dot(&q[1]), &[p1, p2]).unwrap();
+        assert_eq!(h.len(), 8);
+        for (k, row) in h.iter().enumerate() {
+            assert_eq!(row.len(), 8);
+            for (l, h_kl) in row.iter().enumerate() {
+                // only the mixed blocks d^2 / (dp1 dp2) are non-zero
+                let expected = if k / 4 != l / 4 {
+                    metric()[k % 4][l % 4]
+                } else {
+                    0.
+                };
+                assert_eq!(*h_kl, expected, "k = {}, l = {}", k, l);
+            }
+        }
+    }
+
+    #[test]
+    fn split_matches_generic() {
+        let dual = |re: f64, k: usize| {
+            let mut d = DualN::<f64, U9>::from_real(re);
+            for j in 1..9 {
+                d[j] = (j * k % 7) as f64 - 3.;
+            }
+            d
+        };
+        let p1 = LorentzVector::from_args(dual(4., 1), dual(1., 2), dual(2., 3), dual(3., 4));
+        let p2 = LorentzVector::from_args(dual(5., 5), dual(-1., 6), dual(0., 7), dual(2., 8));
+
+        // all products are small integers, so both paths are exact
+        let (split, generic) = (p1.dot_split(&p2), p1.dot(&p2));
+        let (square_split, square) = (p1.square_split(), p1.square());
+        for j in 0..9 {
+            assert_eq!(split[j], generic[j], "component {}", j);
+            assert_eq!(square_split[j], square[j], "component {}", j);
+        }
+    }
+}
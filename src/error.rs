@@ -0,0 +1,81 @@
+use std::fmt;
+
+/// Errors returned by the fallible operations of this crate.
+/// Operations that panic on invalid input say so in their documentation and
+/// have a `try_` or `checked_` counterpart that returns this type instead.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// A slice did not have the number of components required.
+    BadSliceLength { expected: usize, found: usize },
+    /// A boost vector with a velocity of at least the speed of light.
+    Superluminal { beta_squared: f64 },
+    /// An iterative procedure did not reach the requested accuracy.
+    NonConvergent { iterations: usize, residual: f64 },
+    /// Momentum conservation is violated by more than the tolerance.
+    ConservationViolation { residual: f64, tolerance: f64 },
+    /// A string could not be parsed.
+    ParseError(String),
+    /// An index was out of range of a slice of length `len`.
+    IndexOutOfRange { index: usize, len: usize },
+    /// A linear system has a singular matrix.
+    SingularMatrix,
+    /// A component of a vector cannot be represented in the target type of a cast.
+    Unrepresentable { component: usize },
+    /// The dual numbers have fewer components than the number of derivatives requested.
+    DualDimension { required: usize, found: usize },
+    /// A parameter is out of its allowed range.
+    InvalidParameter(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::BadSliceLength { expected, found } => write!(
+                f,
+                "Bad slice length: expected {} components, found {}",
+                expected, found
+            ),
+            Error::Superluminal { beta_squared } => write!(
+                f,
+                "Superluminal boost: beta^2 = {} is not smaller than 1",
+                beta_squared
+            ),
+            Error::NonConvergent {
+                iterations,
+                residual,
+            } => write!(
+                f,
+                "No convergence after {} iterations: residual {:e}",
+                iterations, residual
+            ),
+            Error::ConservationViolation {
+                residual,
+                tolerance,
+            } => write!(
+                f,
+                "Momentum conservation violated: residual {:e} exceeds tolerance {:e}",
+                residual, tolerance
+            ),
+            Error::ParseError(s) => write!(f, "Parse error: {}", s),
+            Error::IndexOutOfRange { index, len } => write!(
+                f,
+                "Index {} is out of range for a slice of length {}",
+                index, len
+            ),
+            Error::SingularMatrix => write!(f, "Singular matrix"),
+            Error::Unrepresentable { component } => write!(
+                f,
+                "Component {} cannot be represented in the target type",
+                component
+            ),
+            Error::DualDimension { required, found } => write!(
+                f,
+                "Dual numbers with {} components are needed, found {}",
+                required, found
+            ),
+            Error::InvalidParameter(s) => write!(f, "Invalid parameter: {}", s),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,772 @@
+use crate::{Error, Field, LorentzVector};
+use num::Float;
+
+/// Strategy used to absorb a small violation of momentum conservation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ConservationStrategy {
+    /// Add the full violation to the particle with the highest energy.
+    HighestEnergy,
+    /// Distribute the violation over all particles, proportionally to their energy.
+    EnergyWeighted,
+}
+
+/// Check that a slice has as many elements as the momenta of the event.
+fn check_length(expected: usize, found: usize) -> Result<(), Error> {
+    if expected != found {
+        return Err(Error::BadSliceLength { expected, found });
+    }
+    Ok(())
+}
+
+/// Apply charge conjugation to an event, replacing every particle id by the id
+/// of its antiparticle as given by `flip`. The momenta are unchanged.
+pub fn charge_conjugate<F: Fn(i32) -> i32>(ids: &mut [i32], flip: F) {
+    for id in ids {
+        *id = flip(*id);
+    }
+}
+
+/// Apply a parity transformation to an event.
+pub fn parity_transform<T: Field>(momenta: &mut [LorentzVector<T>]) {
+    LorentzVector::apply_parity(momenta);
+}
+
+/// Apply a CP transformation to an event, with the particle ids of the
+/// antiparticles given by `flip`. Returns an error if the number of momenta and
+/// particle ids differ.
+pub fn cp_transform<T: Field, F: Fn(i32) -> i32>(
+    momenta: &mut [LorentzVector<T>],
+    ids: &mut [i32],
+    flip: F,
+) -> Result<(), Error> {
+    check_length(momenta.len(), ids.len())?;
+    parity_transform(momenta);
+    charge_conjugate(ids, flip);
+    Ok(())
+}
+
+/// Cross particle `i` between the initial and the final state, for evaluating a crossed
+/// channel. The momentum is replaced by its negative, the particle id by that of its
+/// antiparticle as given by `flip`, and the crossing is toggled in `crossed`. Crossing
+/// the incoming particles of a physical event yields the all-outgoing convention, in
+/// which the momenta sum to zero. Returns an error if the slices have different
+/// lengths or if `i` is out of range.
+pub fn cross<T: Field, F: Fn(i32) -> i32>(
+    momenta: &mut [LorentzVector<T>],
+    ids: &mut [i32],
+    crossed: &mut [bool],
+    i: usize,
+    flip: F,
+) -> Result<(), Error> {
+    check_length(momenta.len(), ids.len())?;
+    check_length(momenta.len(), crossed.len())?;
+    if i >= momenta.len() {
+        return Err(Error::IndexOutOfRange {
+            index: i,
+            len: momenta.len(),
+        });
+    }
+    momenta[i] = -momenta[i];
+    ids[i] = flip(ids[i]);
+    crossed[i] = !crossed[i];
+    Ok(())
+}
+
+/// Get the physical momenta of an event with crossed particles, by undoing the
+/// negation of the crossed momenta. Returns an error if the number of momenta and
+/// crossing flags differ.
+pub fn physical_momenta<T: Field>(
+    momenta: &[LorentzVector<T>],
+    crossed: &[bool],
+) -> Result<Vec<LorentzVector<T>>, Error> {
+    check_length(momenta.len(), crossed.len())?;
+    Ok(momenta
+        .iter()
+        .zip(crossed)
+        .map(|(p, c)| if *c { -p } else { *p })
+        .collect())
+}
+
+/// Modify the momenta such that they sum exactly to `total`, for cleaning up accumulated
+/// floating-point drift. An error is returned if the violation, relative to the
+/// Euclidean norm of `total`, exceeds `tolerance`.
+pub fn restore_exact_conservation<T: Float + Field>(
+    momenta: &mut [LorentzVector<T>],
+    total: &LorentzVector<T>,
+    strategy: ConservationStrategy,
+    tolerance: T,
+) -> Result<(), Error> {
+    let mut residual = *total;
+    for p in momenta.iter() {
+        residual -= p;
+    }
+
+    let norm = total.euclidean_distance();
+    let violation = if norm > T::zero() {
+        residual.euclidean_distance() / norm
+    } else {
+        residual.euclidean_distance()
+    };
+
+    if violation > tolerance || (momenta.is_empty() && violation > T::zero()) {
+        return Err(Error::ConservationViolation {
+            residual: violation.to_f64().unwrap_or(f64::NAN),
+            tolerance: tolerance.to_f64().unwrap_or(f64::NAN),
+        });
+    }
+
+    match strategy {
+        ConservationStrategy::HighestEnergy => {
+            if let Some(p) = momenta
+                .iter_mut()
+                .max_by(|a, b| a.t.partial_cmp(&b.t).unwrap_or(std::cmp::Ordering::Equal))
+            {
+                *p += residual;
+            }
+        }
+        ConservationStrategy::EnergyWeighted => {
+            let e_sum = momenta
+                .iter()
+                .map(|p| p.t.abs())
+                .fold(T::zero(), |a, e| a + e);
+            if e_sum > T::zero() {
+                for p in momenta.iter_mut() {
+                    *p += residual * (p.t.abs() / e_sum);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Randomly perturb an event while preserving the total momentum and the mass of every
+/// particle, up to rounding errors. Every particle is paired with a random partner,
+/// and the pair is rotated by an angle of at most `max_angle` around a random axis
+/// in its rest frame. Pairs that have no rest frame are left unchanged.
+/// The closure `rng` should produce uniform random numbers in `[0, 1)`.
+pub fn perturb<T: Float + Field, R: FnMut() -> T>(
+    momenta: &mut [LorentzVector<T>],
+    max_angle: T,
+    mut rng: R,
+) {
+    let n = momenta.len();
+    if n < 2 {
+        return;
+    }
+
+    let two = T::one() + T::one();
+    let two_pi = T::from(2. * std::f64::consts::PI).unwrap();
+    for i in 0..n {
+        let offset = (rng() * T::from(n - 1).unwrap()).to_usize().unwrap_or(0);
+        let j = (i + 1 + offset.min(n - 2)) % n;
+
+        let total = momenta[i] + momenta[j];
+        if total.t <= total.spatial_distance() {
+            continue;
+        }
+        let boost_vector = total.beta_vector();
+
+        let cos_theta = two * rng() - T::one();
+        let sin_theta = (T::one() - cos_theta * cos_theta).max(T::zero()).sqrt();
+        let (sin_phi, cos_phi) = (two_pi * rng()).sin_cos();
+        let axis = LorentzVector::from_args(
+            T::zero(),
+            sin_theta * cos_phi,
+            sin_theta * sin_phi,
+            cos_theta,
+        );
+        let angle = max_angle * (two * rng() - T::one());
+
+        for k in [i, j].iter() {
+            let rest = momenta[*k].boost_to_rest_frame_of(&total);
+            momenta[*k] = rest.rotate_axis_angle(&axis, angle).boost(&boost_vector);
+        }
+    }
+}
+
+/// Construct dressed leptons by adding every photon (id 22) to the closest charged
+/// lepton (electron or muon) if it lies within `delta_r` of it. Each photon is used
+/// at most once. Returns the index of every lepton together with its dressed momentum,
+/// or an error if the number of momenta and particle ids differ.
+pub fn dress_leptons<T: Float + Field>(
+    momenta: &[LorentzVector<T>],
+    ids: &[i32],
+    delta_r: T,
+) -> Result<Vec<(usize, LorentzVector<T>)>, Error> {
+    check_length(momenta.len(), ids.len())?;
+
+    let mut leptons: Vec<(usize, LorentzVector<T>)> = ids
+        .iter()
+        .enumerate()
+        .filter(|(_, id)| id.abs() == 11 || id.abs() == 13)
+        .map(|(i, _)| (i, momenta[i]))
+        .collect();
+
+    for (photon, _) in ids.iter().enumerate().filter(|(_, id)| **id == 22) {
+        let mut closest = None;
+        let mut closest_dr = delta_r;
+        for (j, (lepton, _)) in leptons.iter().enumerate() {
+            // use the atan2-based azimuthal separation, since the cosine in `delta_r()`
+            // can round above 1 for the collinear photons that dressing is meant for
+            let delta_eta = momenta[photon].pseudo_rap() - momenta[*lepton].pseudo_rap();
+            let delta_phi = momenta[photon].delta_phi(&momenta[*lepton]);
+            let dr = (delta_eta * delta_eta + delta_phi * delta_phi).sqrt();
+            if dr < closest_dr {
+                closest_dr = dr;
+                closest = Some(j);
+            }
+        }
+
+        if let Some(j) = closest {
+            leptons[j].1 += momenta[photon];
+        }
+    }
+
+    Ok(leptons)
+}
+
+/// Compute the largest gap in pseudorapidity between the momenta with a transverse
+/// momentum above `pt_min`. Returns `None` if fewer than two momenta pass the threshold.
+pub fn largest_rapidity_gap<T: Float + Field>(
+    momenta: &[LorentzVector<T>],
+    pt_min: T,
+) -> Option<T> {
+    let mut etas: Vec<T> = momenta
+        .iter()
+        .filter(|p| p.pt() > pt_min)
+        .map(|p| p.pseudo_rap())
+        .collect();
+    etas.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    etas.windows(2)
+        .map(|w| w[1] - w[0])
+        .fold(None, |max, gap| match max {
+            Some(m) if m >= gap => Some(m),
+            _ => Some(gap),
+        })
+}
+
+/// Sum the energy of all momenta with a pseudorapidity in `[eta_min, eta_max]`.
+pub fn forward_energy<T: Float + Field>(momenta: &[LorentzVector<T>], eta_min: T, eta_max: T) -> T {
+    momenta
+        .iter()
+        .filter(|p| {
+            let eta = p.pseudo_rap();
+            eta >= eta_min && eta <= eta_max
+        })
+        .fold(T::zero(), |acc, p| acc + p.t)
+}
+
+/// A view of a subset of the particles of an event that keeps track of their
+/// original indices through filtering and sorting, without copying momenta.
+#[derive(Debug, Clone)]
+pub struct Selection<'a, T: Field> {
+    momenta: &'a [LorentzVector<T>],
+    indices: Vec<usize>,
+}
+
+impl<'a, T: Field> Selection<'a, T> {
+    /// Select all particles of an event.
+    pub fn new(momenta: &'a [LorentzVector<T>]) -> Selection<'a, T> {
+        Selection {
+            momenta,
+            indices: (0..momenta.len()).collect(),
+        }
+    }
+
+    /// Select the particles with the given indices. Returns an error if an index is
+    /// out of range of the event.
+    pub fn from_indices(
+        momenta: &'a [LorentzVector<T>],
+        indices: Vec<usize>,
+    ) -> Result<Selection<'a, T>, Error> {
+        if let Some(index) = indices.iter().find(|i| **i >= momenta.len()) {
+            return Err(Error::IndexOutOfRange {
+                index: *index,
+                len: momenta.len(),
+            });
+        }
+        Ok(Selection { momenta, indices })
+    }
+
+    /// Keep the particles for which `f` returns true. The closure receives the
+    /// original index and the momentum.
+    pub fn filter<F: FnMut(usize, &LorentzVector<T>) -> bool>(
+        mut self,
+        mut f: F,
+    ) -> Selection<'a, T> {
+        let momenta = self.momenta;
+        self.indices.retain(|i| f(*i, &momenta[*i]));
+        self
+    }
+
+    /// Sort the selected particles with a comparison function on their momenta.
+    pub fn sort_by<F: FnMut(&LorentzVector<T>, &LorentzVector<T>) -> std::cmp::Ordering>(
+        mut self,
+        mut f: F,
+    ) -> Selection<'a, T> {
+        let momenta = self.momenta;
+        self.indices.sort_by(|a, b| f(&momenta[*a], &momenta[*b]));
+        self
+    }
+
+    /// Keep at most the first `n` selected particles.
+    pub fn truncate(mut self, n: usize) -> Selection<'a, T> {
+        self.indices.truncate(n);
+        self
+    }
+
+    /// The original indices of the selected particles.
+    #[inline]
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// The full event the selection refers to.
+    #[inline]
+    pub fn event(&self) -> &'a [LorentzVector<T>] {
+        self.momenta
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Get the original index and the momentum of the `i`th selected particle.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<(usize, &'a LorentzVector<T>)> {
+        let momenta = self.momenta;
+        self.indices.get(i).map(|j| (*j, &momenta[*j]))
+    }
+
+    /// Iterate over the original indices and momenta of the selected particles.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &'a LorentzVector<T>)> + '_ {
+        let momenta = self.momenta;
+        self.indices.iter().map(move |i| (*i, &momenta[*i]))
+    }
+
+    /// Sum the momenta of the selected particles.
+    pub fn sum(&self) -> LorentzVector<T> {
+        self.iter().map(|(_, p)| p).sum()
+    }
+}
+
+impl<'a, T: Float + Field> Selection<'a, T> {
+    /// Sort the selected particles by decreasing transverse momentum.
+    pub fn sort_by_pt(self) -> Selection<'a, T> {
+        self.sort_by(|a, b| {
+            b.pt()
+                .partial_cmp(&a.pt())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// Compute the efficiency of every selected particle, where `efficiency` is a function
+    /// of the transverse momentum and the pseudorapidity.
+    pub fn efficiencies<F: Fn(T, T) -> T>(&self, efficiency: F) -> Vec<T> {
+        self.iter()
+            .map(|(_, p)| efficiency(p.pt(), p.pseudo_rap()))
+            .collect()
+    }
+
+    /// Compute the event weight for detecting all selected particles, which is the
+    /// product of their efficiencies.
+    pub fn event_efficiency<F: Fn(T, T) -> T>(&self, efficiency: F) -> T {
+        self.iter()
+            .fold(T::one(), |w, (_, p)| w * efficiency(p.pt(), p.pseudo_rap()))
+    }
+
+    /// Keep every selected particle with a probability given by its efficiency.
+    /// The closure `rng` should produce uniform random numbers in `[0, 1)`.
+    pub fn sample_acceptance<F: Fn(T, T) -> T, R: FnMut() -> T>(
+        self,
+        efficiency: F,
+        mut rng: R,
+    ) -> Selection<'a, T> {
+        self.filter(|_, p| rng() < efficiency(p.pt(), p.pseudo_rap()))
+    }
+}
+
+/// Round the components of a momentum to integer multiples of `precision`.
+/// NaNs are mapped to `i64::MIN` and out-of-range values saturate.
+fn canonical_key<T: Float + Field>(p: &LorentzVector<T>, precision: T) -> [i64; 4] {
+    let round = |c: T| {
+        let r = (c / precision).round();
+        if r.is_nan() {
+            i64::MIN
+        } else {
+            r.to_i64().unwrap_or(if r > T::zero() {
+                i64::MAX
+            } else {
+                i64::MIN + 1
+            })
+        }
+    };
+    [round(p.t), round(p.x), round(p.y), round(p.z)]
+}
+
+/// Compute the sorted rounded components of an event.
+fn canonical_keys<T: Float + Field>(momenta: &[LorentzVector<T>], precision: T) -> Vec<[i64; 4]> {
+    let mut keys: Vec<[i64; 4]> = momenta
+        .iter()
+        .map(|p| canonical_key(p, precision))
+        .collect();
+    keys.sort_unstable();
+    keys
+}
+
+/// Compute the canonical form of an event: every component is rounded to a multiple of
+/// `precision` and the momenta are sorted lexicographically in `(t, x, y, z)`.
+/// Two events that differ only in the order of the momenta or by less than the
+/// precision (away from rounding boundaries) have the same canonical form.
+pub fn canonical_form<T: Float + Field>(
+    momenta: &[LorentzVector<T>],
+    precision: T,
+) -> Vec<LorentzVector<T>> {
+    let keys = canonical_keys(momenta, precision);
+    keys.iter()
+        .map(|k| {
+            let c = |i: i64| T::from(i).unwrap() * precision;
+            LorentzVector::from_args(c(k[0]), c(k[1]), c(k[2]), c(k[3]))
+        })
+        .collect()
+}
+
+/// Compute a hash of the canonical form of an event, for detecting duplicate phase-space
+/// points. The hash is the 64-bit FNV-1a hash of the rounded components and is
+/// stable across platforms and versions.
+pub fn canonical_hash<T: Float + Field>(momenta: &[LorentzVector<T>], precision: T) -> u64 {
+    let keys = canonical_keys(momenta, precision);
+
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut add = |bytes: &[u8]| {
+        for b in bytes {
+            hash ^= u64::from(*b);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    add(&(keys.len() as u64).to_le_bytes());
+    for k in &keys {
+        for c in k {
+            add(&c.to_le_bytes());
+        }
+    }
+    hash
+}
+
+/// The differences between two events, as computed by `diff_events()`.
+#[derive(Debug, Clone)]
+pub struct EventDiff<T: Field> {
+    /// The matched particles as `(i, j, b[j] - a[i])`.
+    pub matched: Vec<(usize, usize, LorentzVector<T>)>,
+    /// The particles of the first event without a match in the second event.
+    pub unmatched_a: Vec<usize>,
+    /// The particles of the second event without a match in the first event.
+    pub unmatched_b: Vec<usize>,
+    /// The difference `b[j]^2 - a[i]^2` of the square of every matched pair, in the
+    /// order of `matched`.
+    pub square_differences: Vec<T>,
+    /// The difference of the invariant masses of the two events.
+    pub invariant_mass_difference: T,
+}
+
+impl<T: Float + Field> EventDiff<T> {
+    /// Check if every particle is matched and the invariant masses of the events agree
+    /// within `tolerance`.
+    pub fn is_match(&self, tolerance: T) -> bool {
+        self.unmatched_a.is_empty()
+            && self.unmatched_b.is_empty()
+            && self.invariant_mass_difference.abs() <= tolerance
+    }
+
+    /// The largest Euclidean norm of the difference of a matched pair.
+    pub fn max_difference(&self) -> T {
+        self.matched
+            .iter()
+            .fold(T::zero(), |m, (_, _, d)| m.max(d.euclidean_distance()))
+    }
+}
+
+/// Compare two events, for example produced by two versions of a generator. Particles are
+/// matched greedily, starting with the closest pair in Euclidean distance, if their
+/// distance is at most `tolerance`. The order of the momenta does not matter.
+pub fn diff_events<T: Float + Field>(
+    a: &[LorentzVector<T>],
+    b: &[LorentzVector<T>],
+    tolerance: T,
+) -> EventDiff<T> {
+    let mut pairs: Vec<(T, usize, usize)> = a
+        .iter()
+        .enumerate()
+        .flat_map(move |(i, p)| {
+            b.iter()
+                .enumerate()
+                .map(move |(j, q)| ((q - p).euclidean_distance(), i, j))
+        })
+        .filter(|(d, _, _)| *d <= tolerance)
+        .collect();
+    pairs.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut used_a = vec![false; a.len()];
+    let mut used_b = vec![false; b.len()];
+    let mut matched = vec![];
+    for (_, i, j) in pairs {
+        if !used_a[i] && !used_b[j] {
+            used_a[i] = true;
+            used_b[j] = true;
+            matched.push((i, j, b[j] - a[i]));
+        }
+    }
+
+    let unmatched = |used: &[bool]| -> Vec<usize> {
+        used.iter()
+            .enumerate()
+            .filter(|(_, u)| !**u)
+            .map(|(i, _)| i)
+            .collect()
+    };
+    let square_differences = matched
+        .iter()
+        .map(|(i, j, _)| b[*j].square() - a[*i].square())
+        .collect();
+
+    EventDiff {
+        unmatched_a: unmatched(&used_a),
+        unmatched_b: unmatched(&used_b),
+        square_differences,
+        matched,
+        invariant_mass_difference: LorentzVector::invariant_mass(b)
+            - LorentzVector::invariant_mass(a),
+    }
+}
+
+/// The frame in which an `EventMixer` stores its events.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MixingFrame {
+    /// Keep the momenta as they are.
+    Lab,
+    /// Boost every event into its own rest frame, so that particles from events with
+    /// a different total momentum are combined in a common frame.
+    RestFrame,
+}
+
+/// A pool of recent events from which particles of different events are combined,
+/// to build combinatorial-background samples.
+#[derive(Debug, Clone)]
+pub struct EventMixer<T: Float + Field> {
+    depth: usize,
+    frame: MixingFrame,
+    pool: std::collections::VecDeque<Vec<LorentzVector<T>>>,
+}
+
+impl<T: Float + Field> EventMixer<T> {
+    /// Create a mixer that keeps the last `depth` events.
+    pub fn new(depth: usize, frame: MixingFrame) -> EventMixer<T> {
+        EventMixer {
+            depth,
+            frame,
+            pool: std::collections::VecDeque::with_capacity(depth),
+        }
+    }
+
+    /// Transform an event to the frame of the mixer.
+    pub fn to_frame(&self, momenta: &[LorentzVector<T>]) -> Vec<LorentzVector<T>> {
+        let total: LorentzVector<T> = momenta.iter().sum();
+        if self.frame == MixingFrame::Lab || total.t <= total.spatial_distance() {
+            return momenta.to_vec();
+        }
+        let boost_vector = total.restframe_boost_vector();
+        momenta.iter().map(|p| p.boost(&boost_vector)).collect()
+    }
+
+    /// Add an event to the pool, removing the oldest event if the pool is full.
+    pub fn add_event(&mut self, momenta: &[LorentzVector<T>]) {
+        if self.depth == 0 {
+            return;
+        }
+        if self.pool.len() == self.depth {
+            self.pool.pop_front();
+        }
+        let event = self.to_frame(momenta);
+        self.pool.push_back(event);
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pool.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.pool.clear();
+    }
+
+    /// Iterate over the events in the pool, from old to new.
+    pub fn events(&self) -> impl Iterator<Item = &[LorentzVector<T>]> {
+        self.pool.iter().map(|e| e.as_slice())
+    }
+
+    /// Build a mixed event with one random particle from each of `multiplicity` distinct
+    /// random events of the pool. Returns `None` if the pool has fewer non-empty events.
+    /// The closure `rng` should produce uniform random numbers in `[0, 1)`.
+    pub fn mix<R: FnMut() -> T>(
+        &self,
+        multiplicity: usize,
+        mut rng: R,
+    ) -> Option<Vec<LorentzVector<T>>> {
+        let mut candidates: Vec<&Vec<LorentzVector<T>>> =
+            self.pool.iter().filter(|e| !e.is_empty()).collect();
+        if candidates.len() < multiplicity {
+            return None;
+        }
+
+        let mut pick = |n: usize| {
+            (rng() * T::from(n).unwrap())
+                .to_usize()
+                .unwrap_or(0)
+                .min(n - 1)
+        };
+
+        let mut mixed = Vec::with_capacity(multiplicity);
+        for i in 0..multiplicity {
+            // partial Fisher-Yates shuffle, so that every event is used at most once
+            let j = i + pick(candidates.len() - i);
+            candidates.swap(i, j);
+            let event = candidates[i];
+            mixed.push(event[pick(event.len())]);
+        }
+        Some(mixed)
+    }
+
+    /// Combine every particle of `momenta` with every particle in the pool.
+    /// The event is transformed to the frame of the mixer first; it is not added to the pool.
+    pub fn mixed_pairs(
+        &self,
+        momenta: &[LorentzVector<T>],
+    ) -> Vec<(LorentzVector<T>, LorentzVector<T>)> {
+        let event = self.to_frame(momenta);
+        let mut pairs = Vec::new();
+        for p in &event {
+            for q in self.pool.iter().flatten() {
+                pairs.push((*p, *q));
+            }
+        }
+        pairs
+    }
+}
+
+/// Draw a number from a standard normal distribution with the Box-Muller method,
+/// using uniform random numbers in `[0, 1)`.
+pub(crate) fn normal<T: Float, R: FnMut() -> T>(rng: &mut R) -> T {
+    let two = T::one() + T::one();
+    let two_pi = T::from(2. * std::f64::consts::PI).unwrap();
+    let r = (-two * (T::one() - rng()).ln()).sqrt();
+    r * (two_pi * rng()).cos()
+}
+
+/// A hard event overlaid with pileup interactions.
+#[derive(Debug, Clone)]
+pub struct PileupEvent<T: Field> {
+    /// The momenta of all particles, starting with those of the hard event.
+    pub momenta: Vec<LorentzVector<T>>,
+    /// The interaction of every particle: 0 for the hard event and `k` for the `k`th
+    /// pileup interaction.
+    pub origin: Vec<usize>,
+    /// The position of the vertex of every interaction, starting with the hard event.
+    pub vertices: Vec<LorentzVector<T>>,
+}
+
+impl<T: Field> PileupEvent<T> {
+    /// Iterate over the momenta of the particles of the hard event.
+    pub fn hard_momenta(&self) -> impl Iterator<Item = &LorentzVector<T>> {
+        self.momenta
+            .iter()
+            .zip(&self.origin)
+            .filter(|(_, o)| **o == 0)
+            .map(|(p, _)| p)
+    }
+
+    /// Get the position of the vertex from which particle `i` originates.
+    #[inline]
+    pub fn vertex_of(&self, i: usize) -> &LorentzVector<T> {
+        &self.vertices[self.origin[i]]
+    }
+}
+
+/// Overlay the momenta of pileup interactions, for example generated by a toy
+/// generator or read from a minimum-bias sample, onto a hard event. The vertex of every
+/// pileup interaction is displaced from `hard_vertex` along the beam axis by a normally
+/// distributed distance with width `sigma_z`, and in time by an independent amount
+/// with the same width.
+/// The closure `rng` should produce uniform random numbers in `[0, 1)`.
+pub fn overlay_pileup<'a, T: Float + Field + 'a, I, R>(
+    hard: &[LorentzVector<T>],
+    hard_vertex: &LorentzVector<T>,
+    pileup: I,
+    sigma_z: T,
+    mut rng: R,
+) -> PileupEvent<T>
+where
+    I: IntoIterator<Item = &'a [LorentzVector<T>]>,
+    R: FnMut() -> T,
+{
+    let mut event = PileupEvent {
+        momenta: hard.to_vec(),
+        origin: vec![0; hard.len()],
+        vertices: vec![*hard_vertex],
+    };
+
+    for (k, momenta) in pileup.into_iter().enumerate() {
+        let dz = sigma_z * normal(&mut rng);
+        let dt = sigma_z * normal(&mut rng);
+        event
+            .vertices
+            .push(*hard_vertex + LorentzVector::from_args(dt, T::zero(), T::zero(), dz));
+        event.momenta.extend_from_slice(momenta);
+        event
+            .origin
+            .extend(std::iter::repeat(k + 1).take(momenta.len()));
+    }
+
+    event
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dress_collinear_photon() {
+        // the cosine of the azimuthal separation of this pair rounds to 1 + 4e-16
+        let lepton = LorentzVector::from_args(20f64, 16.344123419196492, 8.190722644600882, 5.);
+        let photon = LorentzVector::from_args(19., 15.486952182196331, 7.7611583491580545, 4.7);
+        let far = LorentzVector::from_args(10., -6., 0., 8.);
+        let momenta = [lepton, photon, far];
+
+        let dressed = dress_leptons(&momenta, &[11, 22, 22], 0.1).unwrap();
+        assert_eq!(dressed.len(), 1);
+        assert_eq!(dressed[0].0, 0);
+        let p = dressed[0].1;
+        let q = lepton + photon;
+        assert_eq!((p.t, p.x, p.y, p.z), (q.t, q.x, q.y, q.z));
+
+        assert_eq!(
+            dress_leptons(&momenta, &[11, 22], 0.1).unwrap_err(),
+            Error::BadSliceLength {
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+}
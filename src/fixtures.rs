@@ -0,0 +1,219 @@
+use crate::{Error, Field, LorentzVector};
+use num::Float;
+use std::fmt;
+use std::str::FromStr;
+
+/// A kinematic configuration together with its invariants, used to validate a build
+/// against known-good numbers. Generate the reference values in `f128` and compare
+/// them with the values computed in the precision under test.
+#[derive(Debug, Clone)]
+pub struct ReferenceConfiguration<T: Field> {
+    pub momenta: Vec<LorentzVector<T>>,
+    /// All Minkowski products `p_i.p_j`.
+    pub dot_matrix: Vec<Vec<T>>,
+    pub eta: Vec<T>,
+    pub phi: Vec<T>,
+}
+
+impl<T: Float + Field> ReferenceConfiguration<T> {
+    /// Compute the invariants of a configuration.
+    pub fn new(momenta: Vec<LorentzVector<T>>) -> ReferenceConfiguration<T> {
+        let dot_matrix = momenta
+            .iter()
+            .map(|p| momenta.iter().map(|q| p.dot(q)).collect())
+            .collect();
+        let eta = momenta.iter().map(|p| p.pseudo_rap()).collect();
+        let phi = momenta.iter().map(|p| p.phi()).collect();
+        ReferenceConfiguration {
+            momenta,
+            dot_matrix,
+            eta,
+            phi,
+        }
+    }
+
+    /// The standard set of reference configurations: a 2 -> 2 scattering, a massive
+    /// 2 -> 3 production, a forward configuration and a nearly collinear pair.
+    /// All components are exactly representable, so the invariants only depend
+    /// on the precision of `T`.
+    pub fn standard_set() -> Vec<ReferenceConfiguration<T>> {
+        let configurations: [&[[f64; 4]]; 4] = [
+            &[
+                [450., 0., 0., 450.],
+                [450., 0., 0., -450.],
+                [450., 150., 300., 300.],
+                [450., -150., -300., -300.],
+            ],
+            &[
+                [750., 0., 0., 750.],
+                [750., 0., 0., -750.],
+                [410.5, 112.25, -87.5, 295.75],
+                [520.125, -301., 154.5, -211.375],
+                [569.375, 188.75, -67., -84.375],
+            ],
+            &[[10000., 0.5, -0.75, 9999.5], [3.5, 1.25, 2.5, -1.75]],
+            &[
+                [100., 30., 40., 86.0],
+                [100.0009765625, 30.0009765625, 40., 86.0],
+            ],
+        ];
+
+        configurations
+            .iter()
+            .map(|c| {
+                ReferenceConfiguration::new(
+                    c.iter()
+                        .map(|p| {
+                            LorentzVector::from_args(
+                                T::from(p[0]).unwrap(),
+                                T::from(p[1]).unwrap(),
+                                T::from(p[2]).unwrap(),
+                                T::from(p[3]).unwrap(),
+                            )
+                        })
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Compute the largest relative deviation of the invariants of `other` from
+    /// the invariants of this configuration.
+    pub fn max_relative_deviation(&self, other: &ReferenceConfiguration<T>) -> T {
+        let rel = |a: T, b: T| {
+            if a == b {
+                T::zero()
+            } else {
+                (a - b).abs() / a.abs().max(b.abs())
+            }
+        };
+
+        let mut max = T::zero();
+        for (ra, rb) in self.dot_matrix.iter().zip(&other.dot_matrix) {
+            for (a, b) in ra.iter().zip(rb) {
+                max = max.max(rel(*a, *b));
+            }
+        }
+        for (a, b) in self.eta.iter().zip(&other.eta) {
+            max = max.max(rel(*a, *b));
+        }
+        for (a, b) in self.phi.iter().zip(&other.phi) {
+            max = max.max(rel(*a, *b));
+        }
+        max
+    }
+}
+
+impl<T: Field> fmt::Display for ReferenceConfiguration<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, p) in self.momenta.iter().enumerate() {
+            writeln!(f, "p {} {} {} {} {}", i, p.t, p.x, p.y, p.z)?;
+        }
+        for (i, r) in self.dot_matrix.iter().enumerate() {
+            for (j, d) in r.iter().enumerate() {
+                writeln!(f, "dot {} {} {}", i, j, d)?;
+            }
+        }
+        for (i, e) in self.eta.iter().enumerate() {
+            writeln!(f, "eta {} {}", i, e)?;
+        }
+        for (i, e) in self.phi.iter().enumerate() {
+            writeln!(f, "phi {} {}", i, e)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Field + FromStr> FromStr for ReferenceConfiguration<T> {
+    type Err = Error;
+
+    /// Read a configuration in the format written by `Display`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut momenta = vec![];
+        let mut dots = vec![];
+        let mut eta = vec![];
+        let mut phi = vec![];
+
+        let parse = |v: &str| {
+            v.parse::<T>()
+                .map_err(|_| Error::ParseError(format!("Cannot parse number '{}'", v)))
+        };
+
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match (fields[0], fields.len()) {
+                ("p", 6) => momenta.push(LorentzVector::from_args(
+                    parse(fields[2])?,
+                    parse(fields[3])?,
+                    parse(fields[4])?,
+                    parse(fields[5])?,
+                )),
+                ("dot", 4) => dots.push(parse(fields[3])?),
+                ("eta", 3) => eta.push(parse(fields[2])?),
+                ("phi", 3) => phi.push(parse(fields[2])?),
+                _ => return Err(Error::ParseError(format!("Invalid line '{}'", line))),
+            }
+        }
+
+        let n = momenta.len();
+        if dots.len() != n * n || eta.len() != n || phi.len() != n {
+            return Err(Error::ParseError(
+                "Number of invariants does not match the number of momenta".to_owned(),
+            ));
+        }
+
+        Ok(ReferenceConfiguration {
+            momenta,
+            dot_matrix: dots.chunks(n.max(1)).map(|c| c.to_vec()).collect(),
+            eta,
+            phi,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_set_masses() {
+        let expected: [&[f64]; 4] = [
+            &[0., 0., 0., 0.],
+            &[0., 0., 60785.875, 111379.375, 276953.1875],
+            &[9998.9375, 1.375],
+            &[104., 104.13671875],
+        ];
+        let set = ReferenceConfiguration::<f64>::standard_set();
+        assert_eq!(set.len(), expected.len());
+        for (c, masses) in set.iter().zip(expected.iter()) {
+            assert_eq!(c.momenta.len(), masses.len());
+            for (p, m2) in c.momenta.iter().zip(masses.iter()) {
+                assert!((p.square() - m2).abs() <= 1e-12 * p.t * p.t);
+            }
+        }
+    }
+
+    #[test]
+    fn standard_set_conservation() {
+        // the first two configurations are scattering processes with two incoming momenta
+        for c in &ReferenceConfiguration::<f64>::standard_set()[..2] {
+            let incoming: LorentzVector<f64> = c.momenta[..2].iter().sum();
+            let outgoing: LorentzVector<f64> = c.momenta[2..].iter().sum();
+            assert!((incoming - outgoing).euclidean_distance() <= 1e-12 * incoming.t);
+        }
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for c in ReferenceConfiguration::<f64>::standard_set() {
+            let r: ReferenceConfiguration<f64> = c.to_string().parse().unwrap();
+            for (p, q) in c.momenta.iter().zip(&r.momenta) {
+                assert_eq!((p.t, p.x, p.y, p.z), (q.t, q.x, q.y, q.z));
+            }
+            assert_eq!(c.dot_matrix, r.dot_matrix);
+            assert_eq!(c.eta, r.eta);
+            assert_eq!(c.phi, r.phi);
+            assert_eq!(c.max_relative_deviation(&r), 0.);
+        }
+    }
+}
@@ -0,0 +1,396 @@
+use crate::event::normal;
+use crate::kinematics::two_body_decay_momentum;
+use crate::{Error, Field, LorentzVector};
+use num::Float;
+
+/// The distribution of the number of particles in an event.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Multiplicity<T: Float> {
+    Fixed(usize),
+    Poisson {
+        mean: T,
+    },
+    /// A negative binomial distribution with shape `k`, which describes the broad,
+    /// approximately KNO-scaling multiplicity distributions of hadron collisions.
+    /// The variance is `mean + mean^2 / k`.
+    NegativeBinomial {
+        mean: T,
+        k: T,
+    },
+}
+
+/// The transverse-momentum spectrum of the particles.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Spectrum<T: Float> {
+    /// `dN/dpt ~ pt exp(-mt / temperature)`.
+    Thermal { temperature: T },
+    /// `dN/dpt ~ pt (1 + (q - 1) mt / temperature)^(-1 / (q - 1))`, with `1 < q < 1.5`.
+    /// This has a power-law tail and becomes thermal for `q -> 1`.
+    Tsallis { temperature: T, q: T },
+}
+
+/// A generator of toy events with a configurable multiplicity distribution and soft
+/// transverse-momentum spectrum, uniform in pseudorapidity and azimuthal angle. The
+/// events are not physical, since the momenta are independent and not conserved, but
+/// they are realistic inputs for testing clustering and observables.
+/// The distributions are validated on construction and cannot be changed afterwards.
+#[derive(Debug, Copy, Clone)]
+pub struct ToyGenerator<T: Float> {
+    multiplicity: Multiplicity<T>,
+    spectrum: Spectrum<T>,
+    /// The mass of every particle.
+    pub mass: T,
+    /// The particles are generated with a pseudorapidity in `[-eta_max, eta_max]`.
+    pub eta_max: T,
+}
+
+impl<T: Float + Field> ToyGenerator<T> {
+    /// Create a generator of massless particles with `|eta| <= 2.5`. Returns an error if a
+    /// parameter of the distributions is out of range or not finite, for which the sampling
+    /// would not terminate or produce garbage.
+    pub fn new(
+        multiplicity: Multiplicity<T>,
+        spectrum: Spectrum<T>,
+    ) -> Result<ToyGenerator<T>, Error> {
+        let invalid = |reason: &str| Err(Error::InvalidParameter(reason.to_owned()));
+        let positive = |x: T| x > T::zero() && x.is_finite();
+        let non_negative = |x: T| x >= T::zero() && x.is_finite();
+
+        match multiplicity {
+            Multiplicity::Fixed(_) => {}
+            Multiplicity::Poisson { mean } => {
+                if !non_negative(mean) {
+                    return invalid("Poisson mean is not a finite non-negative number");
+                }
+            }
+            Multiplicity::NegativeBinomial { mean, k } => {
+                if !non_negative(mean) || !positive(k) {
+                    return invalid("negative binomial mean or shape k is out of range");
+                }
+            }
+        }
+
+        match spectrum {
+            Spectrum::Thermal { temperature } => {
+                if !positive(temperature) {
+                    return invalid("temperature is not a finite positive number");
+                }
+            }
+            Spectrum::Tsallis { temperature, q } => {
+                if !positive(temperature) {
+                    return invalid("temperature is not a finite positive number");
+                }
+                let in_range = q > T::one() && q < T::from(1.5).unwrap();
+                if !in_range {
+                    return invalid("Tsallis parameter q is not in (1, 1.5)");
+                }
+            }
+        }
+
+        Ok(ToyGenerator {
+            multiplicity,
+            spectrum,
+            mass: T::zero(),
+            eta_max: T::from(2.5).unwrap(),
+        })
+    }
+
+    /// The distribution of the number of particles.
+    #[inline]
+    pub fn multiplicity(&self) -> Multiplicity<T> {
+        self.multiplicity
+    }
+
+    /// The transverse-momentum spectrum.
+    #[inline]
+    pub fn spectrum(&self) -> Spectrum<T> {
+        self.spectrum
+    }
+
+    /// Draw the number of particles of an event, using uniform random numbers in `[0, 1)`.
+    pub fn sample_multiplicity<R: FnMut() -> T>(&self, rng: &mut R) -> usize {
+        match self.multiplicity {
+            Multiplicity::Fixed(n) => n,
+            Multiplicity::Poisson { mean } => poisson(mean, rng),
+            Multiplicity::NegativeBinomial { mean, k } => {
+                let mean = gamma(k, rng) * mean / k;
+                poisson(mean, rng)
+            }
+        }
+    }
+
+    /// Draw a transverse momentum from the spectrum, using uniform random numbers in `[0, 1)`.
+    /// A mass that is not finite yields NaN, since the mass correction would never accept.
+    pub fn sample_pt<R: FnMut() -> T>(&self, rng: &mut R) -> T {
+        if !self.mass.is_finite() {
+            return T::nan();
+        }
+
+        // sample the massless spectrum, and correct for the mass by rejection,
+        // since the spectrum decreases with mt >= pt
+        let weight = |x: T| match self.spectrum {
+            Spectrum::Thermal { temperature } => (-x / temperature).exp(),
+            Spectrum::Tsallis { temperature, q } => {
+                (T::one() + (q - T::one()) * x / temperature).powf(-(q - T::one()).inv())
+            }
+        };
+
+        loop {
+            let scale = match self.spectrum {
+                Spectrum::Thermal { temperature } => temperature,
+                Spectrum::Tsallis { temperature, q } => {
+                    // a Tsallis distribution is a superposition of thermal ones
+                    let shape = (q - T::one()).inv() - T::from(2.).unwrap();
+                    temperature / ((q - T::one()) * gamma(shape, rng))
+                }
+            };
+            let pt = scale * gamma(T::from(2.).unwrap(), rng);
+            let mt = pt.hypot(self.mass);
+            if self.mass == T::zero() || rng() * weight(pt) <= weight(mt) {
+                return pt;
+            }
+        }
+    }
+
+    /// Generate an event, using uniform random numbers in `[0, 1)`.
+    pub fn generate<R: FnMut() -> T>(&self, rng: &mut R) -> Vec<LorentzVector<T>> {
+        let two = T::from(2.).unwrap();
+        let two_pi = T::from(2. * std::f64::consts::PI).unwrap();
+        let n = self.sample_multiplicity(rng);
+        (0..n)
+            .map(|_| {
+                let pt = self.sample_pt(rng);
+                let eta = self.eta_max * (two * rng() - T::one());
+                let phi = two_pi * rng();
+                LorentzVector::from_pt_eta_phi_m(pt, eta, phi, self.mass)
+            })
+            .collect()
+    }
+}
+
+/// The frame in the rest frame of a vector meson in which the polarization of its decay
+/// is defined. The y-axis is perpendicular to the production plane in both frames.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PolarizationFrame {
+    /// The z-axis is the flight direction of the meson in the center-of-mass frame of the beams.
+    Helicity,
+    /// The z-axis bisects the angle between the first beam and the opposite of the second beam.
+    CollinsSoper,
+}
+
+/// Compute the spatial unit vectors `[x, y, z]` of a polarization frame in the rest frame of
+/// `meson`, reached with `boost_to_rest_frame_of()`, from the beam momenta in the lab frame.
+/// Returns `None` if the meson has no rest frame, or if the helicity frame is requested
+/// for a meson at rest in the center-of-mass frame.
+pub fn polarization_axes<T: Float + Field>(
+    meson: &LorentzVector<T>,
+    beam1: &LorentzVector<T>,
+    beam2: &LorentzVector<T>,
+    frame: PolarizationFrame,
+) -> Option<[LorentzVector<T>; 3]> {
+    if meson.t <= meson.spatial_distance() {
+        return None;
+    }
+
+    let unit = |p: LorentzVector<T>| {
+        let p = LorentzVector::from_args(T::zero(), p.x, p.y, p.z);
+        p * p.spatial_distance().inv()
+    };
+    let b1 = unit(beam1.boost_to_rest_frame_of(meson));
+    let b2 = unit(beam2.boost_to_rest_frame_of(meson));
+    let z = match frame {
+        PolarizationFrame::Helicity => {
+            let direction = -(beam1 + beam2).boost_to_rest_frame_of(meson);
+            if direction.spatial_squared() == T::zero() {
+                return None;
+            }
+            unit(direction)
+        }
+        PolarizationFrame::CollinsSoper => unit(b1 - b2),
+    };
+
+    // the beams are collinear for a meson without transverse momentum, in which case
+    // the orientation of the production plane is arbitrary
+    let mut y = cross(&b2, &b1);
+    if y.spatial_squared() < T::epsilon() {
+        let a = if z.x.abs() < T::from(0.9).unwrap() {
+            LorentzVector::from_args(T::zero(), T::one(), T::zero(), T::zero())
+        } else {
+            LorentzVector::from_args(T::zero(), T::zero(), T::one(), T::zero())
+        };
+        y = cross(&z, &a);
+    }
+    let y = unit(y);
+    Some([cross(&y, &z), y, z])
+}
+
+/// A generator of the lepton pair of a vector meson decay, with the angular distribution
+/// `1 + lambda_theta cos^2(theta) + lambda_phi sin^2(theta) cos(2 phi)
+/// + lambda_theta_phi sin(2 theta) cos(phi)` of the first lepton in a polarization frame.
+#[derive(Debug, Copy, Clone)]
+pub struct DileptonDecay<T: Float> {
+    pub lambda_theta: T,
+    pub lambda_phi: T,
+    pub lambda_theta_phi: T,
+    pub frame: PolarizationFrame,
+    /// The mass of the leptons.
+    pub lepton_mass: T,
+}
+
+impl<T: Float + Field> DileptonDecay<T> {
+    /// Create a generator of an unpolarized decay into massless leptons.
+    pub fn new(frame: PolarizationFrame) -> DileptonDecay<T> {
+        DileptonDecay {
+            lambda_theta: T::zero(),
+            lambda_phi: T::zero(),
+            lambda_theta_phi: T::zero(),
+            frame,
+            lepton_mass: T::zero(),
+        }
+    }
+
+    /// Compute the unnormalized angular distribution.
+    #[inline]
+    pub fn distribution(&self, cos_theta: T, phi: T) -> T {
+        let two = T::from(2.).unwrap();
+        let sin2_theta = T::one() - cos_theta * cos_theta;
+        let sin_theta = sin2_theta.max(T::zero()).sqrt();
+        T::one()
+            + self.lambda_theta * cos_theta * cos_theta
+            + self.lambda_phi * sin2_theta * (two * phi).cos()
+            + self.lambda_theta_phi * two * sin_theta * cos_theta * phi.cos()
+    }
+
+    /// Draw the polar and azimuthal angle `(cos theta, phi)` of the first lepton from the
+    /// angular distribution by rejection, using uniform random numbers in `[0, 1)`.
+    pub fn sample_angles<R: FnMut() -> T>(&self, rng: &mut R) -> (T, T) {
+        let two = T::from(2.).unwrap();
+        let two_pi = T::from(2. * std::f64::consts::PI).unwrap();
+        let max = T::one()
+            + self.lambda_theta.abs()
+            + self.lambda_phi.abs()
+            + self.lambda_theta_phi.abs();
+        loop {
+            let cos_theta = two * rng() - T::one();
+            let phi = two_pi * rng();
+            if rng() * max < self.distribution(cos_theta, phi) {
+                return (cos_theta, phi);
+            }
+        }
+    }
+
+    /// Generate the momenta of the two leptons in the lab frame from the decay of `meson`,
+    /// produced in the collision of `beam1` and `beam2`, using uniform random numbers
+    /// in `[0, 1)`. Returns `None` if the meson is too light to decay into the leptons.
+    pub fn decay<R: FnMut() -> T>(
+        &self,
+        meson: &LorentzVector<T>,
+        beam1: &LorentzVector<T>,
+        beam2: &LorentzVector<T>,
+        rng: &mut R,
+    ) -> Option<(LorentzVector<T>, LorentzVector<T>)> {
+        let [x, y, z] = polarization_axes(meson, beam1, beam2, self.frame)?;
+        let mass = meson.m();
+        let q = two_body_decay_momentum(mass, self.lepton_mass, self.lepton_mass)?;
+
+        let (cos_theta, phi) = self.sample_angles(rng);
+        let sin_theta = (T::one() - cos_theta * cos_theta).max(T::zero()).sqrt();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        let n = x * (q * sin_theta * cos_phi) + y * (q * sin_theta * sin_phi) + z * (q * cos_theta);
+
+        let e = mass / T::from(2.).unwrap();
+        let boost_vector = meson.beta_vector();
+        let l1 = LorentzVector::from_args(e, n.x, n.y, n.z);
+        let l2 = LorentzVector::from_args(e, -n.x, -n.y, -n.z);
+        Some((l1.boost(&boost_vector), l2.boost(&boost_vector)))
+    }
+}
+
+/// Compute the cross product of the spatial parts.
+#[inline]
+fn cross<T: Field>(a: &LorentzVector<T>, b: &LorentzVector<T>) -> LorentzVector<T> {
+    LorentzVector::from_args(
+        T::zero(),
+        a.y * b.z - a.z * b.y,
+        a.z * b.x - a.x * b.z,
+        a.x * b.y - a.y * b.x,
+    )
+}
+
+/// Draw a number from a gamma distribution with shape `shape` and unit scale with the
+/// method of Marsaglia and Tsang.
+fn gamma<T: Float, R: FnMut() -> T>(shape: T, rng: &mut R) -> T {
+    if shape < T::one() {
+        let u = T::one() - rng();
+        return gamma(shape + T::one(), rng) * u.powf(shape.recip());
+    }
+
+    let d = shape - T::from(1. / 3.).unwrap();
+    let c = (T::from(9.).unwrap() * d).sqrt().recip();
+    loop {
+        let x = normal(rng);
+        let v = T::one() + c * x;
+        if v <= T::zero() {
+            continue;
+        }
+        let v = v * v * v;
+        let u = T::one() - rng();
+        if u.ln() < x * x / T::from(2.).unwrap() + d - d * v + d * v.ln() {
+            return d * v;
+        }
+    }
+}
+
+/// Draw a number from a Poisson distribution by multiplying uniform random numbers.
+/// Large means are split into chunks, so that `exp(-mean)` does not underflow.
+fn poisson<T: Float, R: FnMut() -> T>(mean: T, rng: &mut R) -> usize {
+    let chunk = T::from(100.).unwrap();
+    let mut remaining = mean;
+    let mut n = 0;
+    while remaining > T::zero() {
+        let limit = (-remaining.min(chunk)).exp();
+        remaining = remaining - chunk;
+        let mut p = rng();
+        while p > limit {
+            n += 1;
+            p = p * rng();
+        }
+    }
+    n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_parameters() {
+        let thermal = Spectrum::Thermal { temperature: 0.3 };
+        for q in &[1., 1.5, 2., f64::NAN] {
+            let tsallis = Spectrum::Tsallis {
+                temperature: 0.3,
+                q: *q,
+            };
+            assert!(ToyGenerator::new(Multiplicity::Fixed(10), tsallis).is_err());
+        }
+        let tsallis = Spectrum::Tsallis {
+            temperature: 0.3,
+            q: 1.2,
+        };
+        assert!(ToyGenerator::new(Multiplicity::Fixed(10), tsallis).is_ok());
+
+        for temperature in &[0., -1., f64::INFINITY, f64::NAN] {
+            let spectrum = Spectrum::Thermal {
+                temperature: *temperature,
+            };
+            assert!(ToyGenerator::new(Multiplicity::Fixed(10), spectrum).is_err());
+        }
+
+        let poisson = Multiplicity::Poisson {
+            mean: f64::INFINITY,
+        };
+        assert!(ToyGenerator::new(poisson, thermal).is_err());
+        let negative_binomial = Multiplicity::NegativeBinomial { mean: 20., k: 0. };
+        assert!(ToyGenerator::new(negative_binomial, thermal).is_err());
+    }
+}
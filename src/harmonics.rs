@@ -0,0 +1,130 @@
+use crate::stats::WeightedStats;
+use crate::{Field, LorentzVector};
+use num::Float;
+
+/// The index of `Y_l^m` in the output of `real_spherical_harmonics()`.
+#[inline]
+pub fn harmonic_index(l: usize, m: isize) -> usize {
+    assert!(m.unsigned_abs() <= l, "|m| is larger than l");
+    ((l * l + l) as isize + m) as usize
+}
+
+/// Compute the real spherical harmonics `Y_l^m(theta, phi)` for all `l <= l_max` and
+/// `-l <= m <= l`, ordered as `harmonic_index(l, m)`. For `m > 0` the harmonic is
+/// `sqrt(2) N P_l^m(cos theta) cos(m phi)`, for `m < 0` it is
+/// `sqrt(2) N P_l^|m|(cos theta) sin(|m| phi)`, where `N = sqrt((2l + 1) / (4 pi) (l - m)! / (l + m)!)`
+/// and `P_l^m` includes the Condon-Shortley phase.
+pub fn real_spherical_harmonics<T: Float>(l_max: usize, cos_theta: T, phi: T) -> Vec<T> {
+    let n = l_max + 1;
+    let x = cos_theta.max(-T::one()).min(T::one());
+    let sin_theta = ((T::one() - x) * (T::one() + x)).sqrt();
+    let four_pi = T::from(4. * std::f64::consts::PI).unwrap();
+    let sqrt2 = T::from(std::f64::consts::SQRT_2).unwrap();
+    let c = |i: usize| T::from(i).unwrap();
+
+    let mut out = vec![T::zero(); n * n];
+    // P_m^m, updated for increasing m
+    let mut pmm = T::one();
+    for m in 0..n {
+        if m > 0 {
+            pmm = -pmm * c(2 * m - 1) * sin_theta;
+        }
+
+        // the ratio (l - m)! / (l + m)! for l = m
+        let mut ratio = T::one();
+        for k in 1..=2 * m {
+            ratio = ratio / c(k);
+        }
+
+        let (sin_m, cos_m) = (c(m) * phi).sin_cos();
+        let (mut p_prev, mut p) = (T::zero(), pmm);
+        for l in m..n {
+            if l == m + 1 {
+                p_prev = p;
+                p = x * c(2 * m + 1) * pmm;
+            } else if l > m + 1 {
+                let next = (x * c(2 * l - 1) * p - c(l + m - 1) * p_prev) / c(l - m);
+                p_prev = p;
+                p = next;
+            }
+            if l > m {
+                ratio = ratio * c(l - m) / c(l + m);
+            }
+
+            let norm = (c(2 * l + 1) / four_pi * ratio).sqrt();
+            if m == 0 {
+                out[harmonic_index(l, 0)] = norm * p;
+            } else {
+                out[harmonic_index(l, m as isize)] = sqrt2 * norm * p * cos_m;
+                out[harmonic_index(l, -(m as isize))] = sqrt2 * norm * p * sin_m;
+            }
+        }
+    }
+    out
+}
+
+/// An accumulator of the weighted moments `<Y_l^m(theta, phi)>` of the real spherical
+/// harmonics of a direction, for extracting angular coefficients.
+#[derive(Debug, Clone)]
+pub struct SphericalMoments<T: Float> {
+    l_max: usize,
+    moments: Vec<WeightedStats<T>>,
+}
+
+impl<T: Float> SphericalMoments<T> {
+    pub fn new(l_max: usize) -> SphericalMoments<T> {
+        SphericalMoments {
+            l_max,
+            moments: vec![WeightedStats::new(); (l_max + 1) * (l_max + 1)],
+        }
+    }
+
+    #[inline]
+    pub fn l_max(&self) -> usize {
+        self.l_max
+    }
+
+    /// Add the direction with polar angle `acos(cos_theta)` and azimuthal angle `phi`.
+    pub fn add_angles(&mut self, cos_theta: T, phi: T, weight: T) {
+        let y = real_spherical_harmonics(self.l_max, cos_theta, phi);
+        for (s, y) in self.moments.iter_mut().zip(y) {
+            s.add(y, weight);
+        }
+    }
+
+    /// Add the direction of the spatial part of the momentum `p` of a decay product,
+    /// which should already be expressed in the frame of the analysis.
+    pub fn add(&mut self, p: &LorentzVector<T>, weight: T)
+    where
+        T: Field,
+    {
+        self.add_angles(p.cos_theta(), p.phi(), weight);
+    }
+
+    /// Combine with the moments of another accumulator with the same `l_max`.
+    pub fn merge(&mut self, other: &SphericalMoments<T>) {
+        assert_eq!(self.l_max, other.l_max, "Maximal l differs");
+        for (s, o) in self.moments.iter_mut().zip(&other.moments) {
+            s.merge(o);
+        }
+    }
+
+    /// Get the statistics of `Y_l^m`.
+    #[inline]
+    pub fn stats(&self, l: usize, m: isize) -> &WeightedStats<T> {
+        assert!(l <= self.l_max, "l is larger than l_max");
+        &self.moments[harmonic_index(l, m)]
+    }
+
+    /// Get the weighted mean `<Y_l^m>`.
+    #[inline]
+    pub fn moment(&self, l: usize, m: isize) -> T {
+        self.stats(l, m).mean()
+    }
+
+    /// Get the statistical error on `<Y_l^m>`.
+    #[inline]
+    pub fn error(&self, l: usize, m: isize) -> T {
+        self.stats(l, m).error_of_mean()
+    }
+}
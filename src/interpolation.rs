@@ -0,0 +1,153 @@
+use crate::{Error, Field, LorentzVector};
+use num::Float;
+
+/// A C1-continuous cubic Hermite interpolation of a sequence of vectors sampled at
+/// increasing values of a path parameter. The tangents are estimated from the
+/// neighbouring samples (Catmull-Rom), so that no derivatives have to be supplied.
+#[derive(Debug, Clone)]
+pub struct HermiteSpline<T: Float + Field> {
+    parameters: Vec<T>,
+    points: Vec<LorentzVector<T>>,
+    tangents: Vec<LorentzVector<T>>,
+}
+
+impl<T: Float + Field> HermiteSpline<T> {
+    /// Create an interpolation of `points`, sampled at the strictly increasing `parameters`.
+    /// Returns an error if the number of parameters and points differ, if there are no
+    /// points, or if the parameters are not strictly increasing.
+    pub fn new(
+        parameters: Vec<T>,
+        points: Vec<LorentzVector<T>>,
+    ) -> Result<HermiteSpline<T>, Error> {
+        if parameters.len() != points.len() {
+            return Err(Error::BadSliceLength {
+                expected: points.len(),
+                found: parameters.len(),
+            });
+        }
+        if points.is_empty() {
+            return Err(Error::InvalidParameter(
+                "at least one point is required".to_owned(),
+            ));
+        }
+        if !parameters.windows(2).all(|w| w[0] < w[1]) {
+            return Err(Error::InvalidParameter(
+                "parameters are not strictly increasing".to_owned(),
+            ));
+        }
+
+        let n = points.len();
+        let tangents = (0..n)
+            .map(|i| {
+                if n == 1 {
+                    return LorentzVector::new();
+                }
+                let (a, b) = (i.saturating_sub(1), (i + 1).min(n - 1));
+                (points[b] - points[a]) * (parameters[b] - parameters[a]).inv()
+            })
+            .collect();
+
+        Ok(HermiteSpline {
+            parameters,
+            points,
+            tangents,
+        })
+    }
+
+    #[inline]
+    pub fn parameters(&self) -> &[T] {
+        &self.parameters
+    }
+
+    #[inline]
+    pub fn points(&self) -> &[LorentzVector<T>] {
+        &self.points
+    }
+
+    /// Find the segment containing `t` and the local coordinate in it.
+    /// Values outside of the parameter range are extrapolated with the first or last segment.
+    #[inline]
+    fn segment(&self, t: T) -> (usize, T, T) {
+        let n = self.parameters.len();
+        let i = if n < 2 {
+            0
+        } else {
+            let upper = self.parameters.iter().position(|p| *p > t).unwrap_or(n);
+            upper.max(1).min(n - 1) - 1
+        };
+        let h = if n < 2 {
+            T::one()
+        } else {
+            self.parameters[i + 1] - self.parameters[i]
+        };
+        (i, h, (t - self.parameters[i]) / h)
+    }
+
+    /// Evaluate the interpolation at the parameter value `t`.
+    pub fn eval(&self, t: T) -> LorentzVector<T> {
+        if self.points.len() == 1 {
+            return self.points[0];
+        }
+
+        let (i, h, s) = self.segment(t);
+        let (two, three) = (T::from(2.).unwrap(), T::from(3.).unwrap());
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let h00 = two * s3 - three * s2 + T::one();
+        let h10 = s3 - two * s2 + s;
+        let h01 = three * s2 - two * s3;
+        let h11 = s3 - s2;
+
+        self.points[i] * h00
+            + self.tangents[i] * (h10 * h)
+            + self.points[i + 1] * h01
+            + self.tangents[i + 1] * (h11 * h)
+    }
+
+    /// Evaluate the derivative of the interpolation with respect to the parameter at `t`.
+    pub fn derivative(&self, t: T) -> LorentzVector<T> {
+        if self.points.len() == 1 {
+            return LorentzVector::new();
+        }
+
+        let (i, h, s) = self.segment(t);
+        let (two, three, four, six) = (
+            T::from(2.).unwrap(),
+            T::from(3.).unwrap(),
+            T::from(4.).unwrap(),
+            T::from(6.).unwrap(),
+        );
+        let s2 = s * s;
+        let d00 = six * (s2 - s);
+        let d10 = three * s2 - four * s + T::one();
+        let d11 = three * s2 - two * s;
+
+        (self.points[i + 1] - self.points[i]) * (-d00 / h)
+            + self.tangents[i] * d10
+            + self.tangents[i + 1] * d11
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_input() {
+        let p = LorentzVector::from_args(1f64, 0., 0., 1.);
+        assert_eq!(
+            HermiteSpline::new(vec![0., 1.], vec![p]).unwrap_err(),
+            Error::BadSliceLength {
+                expected: 1,
+                found: 2
+            }
+        );
+        assert!(HermiteSpline::<f64>::new(vec![], vec![]).is_err());
+        assert!(HermiteSpline::new(vec![0., 0.], vec![p, p]).is_err());
+        assert!(HermiteSpline::new(vec![0., f64::NAN], vec![p, p]).is_err());
+
+        let spline = HermiteSpline::new(vec![0., 1.], vec![p, p * 3.]).unwrap();
+        let q = spline.eval(0.5);
+        assert_eq!((q.t, q.x, q.y, q.z), (2., 0., 0., 2.));
+    }
+}
@@ -0,0 +1,151 @@
+use num::Float;
+
+/// Convert a polar angle `theta` with respect to the beam axis into pseudorapidity.
+/// Angles on the beam axis (`theta <= 0` or `theta >= pi`) yield `T::max_value()`
+/// and `T::min_value()` respectively, matching `pseudo_rap()`.
+#[inline]
+pub fn eta_from_theta<T: Float>(theta: T) -> T {
+    if theta <= T::zero() {
+        return T::max_value();
+    }
+    if theta >= T::from(std::f64::consts::PI).unwrap() {
+        return T::min_value();
+    }
+    // asinh(cot(theta)) avoids the cancellation of -ln(tan(theta/2)) near the beam axis
+    (theta.cos() / theta.sin()).asinh()
+}
+
+/// Convert a pseudorapidity `eta` into a polar angle in `[0, pi]`.
+#[inline]
+pub fn theta_from_eta<T: Float>(eta: T) -> T {
+    (T::one() + T::one()) * (-eta).exp().atan()
+}
+
+/// Convert a rapidity `y` of a particle with transverse momentum `pt` and mass `m`
+/// into pseudorapidity. For `pt == 0` the particle lies on the beam axis and
+/// `T::max_value()` or `T::min_value()` is returned, depending on the sign of `y`.
+#[inline]
+pub fn eta_from_rapidity<T: Float>(y: T, pt: T, m: T) -> T {
+    if pt == T::zero() {
+        if y > T::zero() {
+            return T::max_value();
+        } else if y < T::zero() {
+            return T::min_value();
+        } else {
+            return T::zero();
+        }
+    }
+    (pt.hypot(m) * y.sinh() / pt).asinh()
+}
+
+/// Convert a pseudorapidity `eta` of a particle with transverse momentum `pt` and mass `m`
+/// into rapidity. A massless particle on the beam axis has `y == eta`.
+#[inline]
+pub fn rapidity_from_eta<T: Float>(eta: T, pt: T, m: T) -> T {
+    let mt = pt.hypot(m);
+    if mt == T::zero() {
+        return eta;
+    }
+    (pt * eta.sinh() / mt).asinh()
+}
+
+/// Compute the kinematically allowed range `(min, max)` of `m12^2` in the Dalitz plot
+/// of the decay of a particle with mass `mass` into particles with masses `m1`, `m2` and `m3`.
+#[inline]
+pub fn dalitz_m12_limits<T: Float>(mass: T, m1: T, m2: T, m3: T) -> (T, T) {
+    ((m1 + m2) * (m1 + m2), (mass - m3) * (mass - m3))
+}
+
+/// Compute the kinematically allowed range `(min, max)` of `m23^2` for a given `m12^2`.
+/// Returns `None` if `m12_squared` lies outside of the range of `dalitz_m12_limits()`.
+pub fn dalitz_m23_limits<T: Float>(m12_squared: T, mass: T, m1: T, m2: T, m3: T) -> Option<(T, T)> {
+    let (min, max) = dalitz_m12_limits(mass, m1, m2, m3);
+    if !(m12_squared >= min && m12_squared <= max) || m12_squared <= T::zero() {
+        return None;
+    }
+
+    // the energies of particles 2 and 3 in the rest frame of the (12) system
+    let two_m12 = m12_squared.sqrt() * (T::one() + T::one());
+    let e2 = (m12_squared - m1 * m1 + m2 * m2) / two_m12;
+    let e3 = (mass * mass - m12_squared - m3 * m3) / two_m12;
+    let p2 = (e2 * e2 - m2 * m2).max(T::zero()).sqrt();
+    let p3 = (e3 * e3 - m3 * m3).max(T::zero()).sqrt();
+
+    let e23 = (e2 + e3) * (e2 + e3);
+    Some((e23 - (p2 + p3) * (p2 + p3), e23 - (p2 - p3) * (p2 - p3)))
+}
+
+/// Check if the point `(m12^2, m23^2)` lies inside the Dalitz plot.
+#[inline]
+pub fn is_inside_dalitz<T: Float>(
+    m12_squared: T,
+    m23_squared: T,
+    mass: T,
+    m1: T,
+    m2: T,
+    m3: T,
+) -> bool {
+    match dalitz_m23_limits(m12_squared, mass, m1, m2, m3) {
+        Some((min, max)) => m23_squared >= min && m23_squared <= max,
+        None => false,
+    }
+}
+
+/// Compute the Källén function `a^2 + b^2 + c^2 - 2ab - 2ac - 2bc`.
+/// It is evaluated as `(a - b - c)^2 - 4bc`, which is more accurate close to threshold.
+#[inline]
+pub fn kallen_lambda<T: Float>(a: T, b: T, c: T) -> T {
+    let d = a - b - c;
+    let four = T::from(4.).unwrap();
+    d * d - four * b * c
+}
+
+/// Compute the magnitude of the momentum of the decay products in the rest frame of a
+/// particle with mass `m` decaying into particles with masses `m1` and `m2`.
+/// Returns `None` if the decay is below threshold.
+#[inline]
+pub fn two_body_decay_momentum<T: Float>(m: T, m1: T, m2: T) -> Option<T> {
+    let sum = m1 + m2;
+    let diff = m1 - m2;
+    // the factorized form of kallen_lambda(m^2, m1^2, m2^2)
+    let lambda = (m - sum) * (m + sum) * (m - diff) * (m + diff);
+    if m <= T::zero() || m < sum || lambda.is_nan() {
+        return None;
+    }
+    Some(lambda.max(T::zero()).sqrt() / (m + m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn theta_eta_round_trip() {
+        for &eta in &[-8f64, -2.5, -0.1, 0., 0.1, 2.5, 8.] {
+            assert!((eta_from_theta(theta_from_eta(eta)) - eta).abs() < 1e-12 * (1. + eta.abs()));
+        }
+        assert_eq!(eta_from_theta(0.), f64::MAX);
+        assert_eq!(eta_from_theta(PI), f64::MIN);
+        assert!(eta_from_theta(PI / 2.).abs() < 1e-15);
+    }
+
+    #[test]
+    fn rapidity_eta_round_trip() {
+        for &(eta, pt, m) in &[
+            (0.5f64, 10., 0.),
+            (2.5, 1., 5.),
+            (-3., 40., 91.),
+            (6., 0.2, 0.14),
+        ] {
+            let y = rapidity_from_eta(eta, pt, m);
+            assert!(y.abs() <= eta.abs() * (1. + 1e-14));
+            assert!((eta_from_rapidity(y, pt, m) - eta).abs() < 1e-12 * (1. + eta.abs()));
+        }
+        // massless particles have equal rapidity and pseudorapidity
+        assert!((rapidity_from_eta(1.7, 3., 0.) - 1.7).abs() < 1e-14);
+        assert_eq!(eta_from_rapidity(1., 0., 1.), f64::MAX);
+        assert_eq!(eta_from_rapidity(-1., 0., 1.), f64::MIN);
+        assert_eq!(rapidity_from_eta(2., 0., 0.), 2.);
+    }
+}
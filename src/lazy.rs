@@ -0,0 +1,168 @@
+use crate::{Field, LorentzVector};
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// A lazily evaluated expression for a vector. A chain such as
+/// `lazy(&a) + &b - lazy(&c) * x + lazy(&d) * y` builds an expression without computing
+/// anything, and `eval()` computes every component in a single pass, without the
+/// intermediate `LorentzVector` temporaries of the eager operators. This pays off when
+/// the components are large, for example for duals with many derivatives.
+pub trait Expr {
+    type Field: Field;
+
+    /// Compute component `i`, where 0 is the time component. Components beyond 3 are zero.
+    fn component(&self, i: usize) -> Self::Field;
+
+    #[inline]
+    fn eval(&self) -> LorentzVector<Self::Field> {
+        LorentzVector::from_args(
+            self.component(0),
+            self.component(1),
+            self.component(2),
+            self.component(3),
+        )
+    }
+
+    /// Evaluate the expression into an existing vector.
+    #[inline]
+    fn eval_into(&self, out: &mut LorentzVector<Self::Field>) {
+        out.t = self.component(0);
+        out.x = self.component(1);
+        out.y = self.component(2);
+        out.z = self.component(3);
+    }
+}
+
+/// Start an expression from a vector.
+#[inline]
+pub fn lazy<T: Field>(v: &LorentzVector<T>) -> Leaf<'_, T> {
+    Leaf { v }
+}
+
+/// A reference to a vector in an expression.
+#[derive(Debug, Copy, Clone)]
+pub struct Leaf<'a, T: Field> {
+    v: &'a LorentzVector<T>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Plus<A: Expr, B: Expr> {
+    a: A,
+    b: B,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Minus<A: Expr, B: Expr> {
+    a: A,
+    b: B,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Negated<A: Expr> {
+    a: A,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct Scaled<A: Expr> {
+    a: A,
+    s: A::Field,
+}
+
+impl<T: Field> Expr for &LorentzVector<T> {
+    type Field = T;
+
+    #[inline]
+    fn component(&self, i: usize) -> T {
+        self.get(i).copied().unwrap_or_default()
+    }
+}
+
+impl<T: Field> Expr for Leaf<'_, T> {
+    type Field = T;
+
+    #[inline]
+    fn component(&self, i: usize) -> T {
+        self.v.get(i).copied().unwrap_or_default()
+    }
+}
+
+impl<A: Expr, B: Expr<Field = A::Field>> Expr for Plus<A, B> {
+    type Field = A::Field;
+
+    #[inline]
+    fn component(&self, i: usize) -> A::Field {
+        self.a.component(i) + self.b.component(i)
+    }
+}
+
+impl<A: Expr, B: Expr<Field = A::Field>> Expr for Minus<A, B> {
+    type Field = A::Field;
+
+    #[inline]
+    fn component(&self, i: usize) -> A::Field {
+        self.a.component(i) - self.b.component(i)
+    }
+}
+
+impl<A: Expr> Expr for Negated<A> {
+    type Field = A::Field;
+
+    #[inline]
+    fn component(&self, i: usize) -> A::Field {
+        -self.a.component(i)
+    }
+}
+
+impl<A: Expr> Expr for Scaled<A> {
+    type Field = A::Field;
+
+    #[inline]
+    fn component(&self, i: usize) -> A::Field {
+        self.a.component(i) * self.s
+    }
+}
+
+macro_rules! impl_expr_ops {
+    ([$($gen:tt)*] $ty:ty, $field:ty) => {
+        impl<$($gen)*, R: Expr<Field = $field>> Add<R> for $ty {
+            type Output = Plus<$ty, R>;
+
+            #[inline]
+            fn add(self, other: R) -> Plus<$ty, R> {
+                Plus { a: self, b: other }
+            }
+        }
+
+        impl<$($gen)*, R: Expr<Field = $field>> Sub<R> for $ty {
+            type Output = Minus<$ty, R>;
+
+            #[inline]
+            fn sub(self, other: R) -> Minus<$ty, R> {
+                Minus { a: self, b: other }
+            }
+        }
+
+        impl<$($gen)*> Mul<$field> for $ty {
+            type Output = Scaled<$ty>;
+
+            #[inline]
+            fn mul(self, other: $field) -> Scaled<$ty> {
+                Scaled { a: self, s: other }
+            }
+        }
+
+        impl<$($gen)*> Neg for $ty {
+            type Output = Negated<$ty>;
+
+            #[inline]
+            fn neg(self) -> Negated<$ty> {
+                Negated { a: self }
+            }
+        }
+    };
+}
+
+impl_expr_ops!(['a, T: Field] Leaf<'a, T>, T);
+impl_expr_ops!([A: Expr, B: Expr<Field = A::Field>] Plus<A, B>, A::Field);
+impl_expr_ops!([A: Expr, B: Expr<Field = A::Field>] Minus<A, B>, A::Field);
+impl_expr_ops!([A: Expr] Negated<A>, A::Field);
+impl_expr_ops!([A: Expr] Scaled<A>, A::Field);
@@ -1,6 +1,6 @@
 #[cfg(feature = "dual_num_support")]
 use dual_num::{Allocator, DefaultAllocator, Dim, DimName, DualN, Owned};
-use num::traits::ops::mul_add::MulAdd;
+use num::traits::ops::mul_add::{MulAdd, MulAddAssign};
 use num::traits::Inv;
 use num::traits::{NumAssign, NumOps, NumRef};
 use num::Complex;
@@ -12,9 +12,75 @@ use num::{NumCast, ToPrimitive};
 use std::fmt;
 use std::fmt::{Debug, Display, LowerExp};
 use std::iter::Sum;
-use std::ops::{Add, AddAssign, Div, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// Report that a numerical stability fallback has been taken.
+/// Events are only emitted when the `tracing_support` feature is enabled.
+macro_rules! instability {
+    ($($arg:tt)+) => {
+        #[cfg(feature = "tracing_support")]
+        tracing::warn!(target: "lorentz_vector::instability", $($arg)+);
+    };
+}
+
+/// Check in debug builds that `error` is negligible compared to `scale`, to catch
+/// regressions in the conventions of transformations.
+/// Checks are only performed when the `consistency_checks` feature is enabled, and
+/// non-finite errors from invalid inputs are ignored.
+macro_rules! consistency_check {
+    ($error:expr, $scale:expr, $($arg:tt)+) => {
+        #[cfg(feature = "consistency_checks")]
+        {
+            let error: T = $error;
+            debug_assert!(
+                !error.is_finite() || error.abs() <= T::epsilon().sqrt() * ($scale),
+                $($arg)+
+            );
+        }
+    };
+}
 
+pub mod audit;
+mod cache;
+pub mod collider;
+mod compact;
 mod deserialize;
+#[cfg(feature = "dual_num_support")]
+pub mod dual;
+mod error;
+pub mod event;
+pub mod fixtures;
+pub mod generator;
+pub mod harmonics;
+pub mod interpolation;
+pub mod kinematics;
+pub mod lazy;
+pub mod linalg;
+pub mod mandelstam;
+pub mod minimize;
+pub mod phase_space;
+#[cfg(any(feature = "pyo3_support", feature = "cpython_support"))]
+mod python;
+pub mod recorder;
+mod rotation;
+mod serialize;
+mod sl2c;
+pub mod stats;
+pub mod threshold;
+mod transformation;
+mod transverse;
+
+pub use crate::cache::InvariantCache;
+pub use crate::compact::CompactMomenta;
+pub use crate::error::Error;
+#[cfg(any(feature = "pyo3_support", feature = "cpython_support"))]
+pub use crate::python::Events;
+pub use crate::rotation::Rotation;
+pub use crate::sl2c::SpinorTransformation;
+pub use crate::transformation::{BoostFromTo, EulerConvention, LorentzTransformation};
+pub use crate::transverse::TransverseVector;
 
 pub trait Field
 where
@@ -111,6 +177,24 @@ where
 {
 }
 
+/// Treatment of the invariant mass when rescaling the energy or momentum of a vector.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MassPolicy {
+    /// Scale all four components, so that the mass is scaled as well.
+    Scale,
+    /// Keep the invariant mass fixed and recompute the remaining components.
+    Preserve,
+}
+
+/// Normalization of the light-cone components `p+` and `p-`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LightConeConvention {
+    /// `p+- = E +- pz`, so that `p^2 = p+ p- - pt^2`.
+    Unnormalized,
+    /// `p+- = (E +- pz) / sqrt(2)`, so that `p^2 = 2 p+ p- - pt^2`.
+    Symmetric,
+}
+
 #[derive(Debug, Copy, Clone)]
 pub struct LorentzVector<T: Field> {
     pub t: T,
@@ -130,6 +214,9 @@ impl<T: Field> Default for LorentzVector<T> {
     }
 }
 
+/// The components are written with the `Display` implementation of `T`, which for `f32`
+/// and `f64` is the shortest representation that parses back to the same value. The
+/// output can be read back with `FromStr`.
 impl<T: Field> Display for LorentzVector<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -150,6 +237,47 @@ impl<T: Field + LowerExp> LowerExp for LorentzVector<T> {
     }
 }
 
+impl<T: Field + FromStr> FromStr for LorentzVector<T> {
+    type Err = Error;
+
+    /// Read a vector in the format written by `Display` or `LowerExp`. For `f32` and `f64`,
+    /// `v.to_string().parse()` recovers the exact bits of `v`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let inner = s
+            .trim()
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .ok_or_else(|| Error::ParseError(format!("Missing parentheses in '{}'", s)))?;
+
+        let mut components = [T::zero(); 4];
+        let mut fields = inner.split(',');
+        for (c, label) in components.iter_mut().zip(&["t", "x", "y", "z"]) {
+            let v = fields
+                .next()
+                .and_then(|f| f.trim().strip_prefix(*label))
+                .and_then(|f| f.strip_prefix(':'))
+                .ok_or_else(|| {
+                    Error::ParseError(format!("Cannot read {}-component of '{}'", label, s))
+                })?;
+            *c = v
+                .trim()
+                .parse::<T>()
+                .map_err(|_| Error::ParseError(format!("Cannot parse number '{}'", v)))?;
+        }
+
+        if fields.next().is_some() {
+            return Err(Error::ParseError(format!("Too many components in '{}'", s)));
+        }
+
+        Ok(LorentzVector::from_args(
+            components[0],
+            components[1],
+            components[2],
+            components[3],
+        ))
+    }
+}
+
 impl<T: Field> LorentzVector<T> {
     #[inline]
     pub fn new() -> LorentzVector<T> {
@@ -166,12 +294,28 @@ impl<T: Field> LorentzVector<T> {
         LorentzVector { t, x, y, z }
     }
 
+    /// Create a vector from the first four components of a slice.
+    /// This panics for shorter slices; use `try_from_slice()` for a fallible version.
     #[inline]
     pub fn from_slice(v: &[T]) -> LorentzVector<T> {
         let (t, x, y, z) = (v[0], v[1], v[2], v[3]);
         LorentzVector { t, x, y, z }
     }
 
+    /// Create a vector from a slice, checking that it has exactly four components.
+    #[inline]
+    pub fn try_from_slice(v: &[T]) -> Result<LorentzVector<T>, Error> {
+        if v.len() != 4 {
+            return Err(Error::BadSliceLength {
+                expected: 4,
+                found: v.len(),
+            });
+        }
+        Ok(LorentzVector::from_args(v[0], v[1], v[2], v[3]))
+    }
+
+    /// Create a vector from the first four components of a vector.
+    /// This panics for shorter vectors; use `try_from_slice()` for a fallible version.
     #[inline]
     pub fn from_vec(v: Vec<T>) -> LorentzVector<T> {
         let (t, x, y, z) = (v[0], v[1], v[2], v[3]);
@@ -188,21 +332,134 @@ impl<T: Field> LorentzVector<T> {
         }
     }
 
+    /// Apply a parity transformation, negating the spatial components.
+    /// This is the same operation as `dual()`.
+    #[inline]
+    pub fn parity(&self) -> LorentzVector<T> {
+        self.dual()
+    }
+
+    /// Apply time reversal to a position vector, negating the time component.
+    #[inline]
+    pub fn time_reversal(&self) -> LorentzVector<T> {
+        LorentzVector {
+            t: -self.t,
+            x: self.x,
+            y: self.y,
+            z: self.z,
+        }
+    }
+
+    /// Reflect the spatial part across the plane through the origin with normal `normal`.
+    /// The time component of `normal` is ignored. A vanishing normal does not define a
+    /// plane, and the vector is returned unchanged.
+    #[inline]
+    pub fn reflect(&self, normal: &LorentzVector<T>) -> LorentzVector<T> {
+        let n2 = normal.spatial_squared();
+        if n2 == T::zero() {
+            return *self;
+        }
+        let f = (self.spatial_dot(normal) + self.spatial_dot(normal)) / n2;
+        LorentzVector {
+            t: self.t,
+            x: self.x - normal.x * f,
+            y: self.y - normal.y * f,
+            z: self.z - normal.z * f,
+        }
+    }
+
+    /// Split the spatial part into the components parallel and perpendicular to the
+    /// spatial part of `axis`, for example to compute the transverse momentum with respect
+    /// to a jet or thrust axis. Both components have a vanishing time component.
+    /// A vanishing axis has no direction, and the full spatial part is perpendicular to it.
+    #[inline]
+    pub fn decompose_along(&self, axis: &LorentzVector<T>) -> (LorentzVector<T>, LorentzVector<T>) {
+        let a2 = axis.spatial_squared();
+        if a2 == T::zero() {
+            return (
+                LorentzVector::new(),
+                LorentzVector::from_args(T::zero(), self.x, self.y, self.z),
+            );
+        }
+        let f = self.spatial_dot(axis) / a2;
+        let parallel = LorentzVector::from_args(T::zero(), axis.x * f, axis.y * f, axis.z * f);
+        let perpendicular = LorentzVector::from_args(
+            T::zero(),
+            self.x - parallel.x,
+            self.y - parallel.y,
+            self.z - parallel.z,
+        );
+        (parallel, perpendicular)
+    }
+
+    /// Decompose the vector as `a * n1 + b * n2 + k`, where the remainder `k` is orthogonal
+    /// to both reference vectors, and return `(a, b, k)`. The reference vectors are typically
+    /// light-like, but only have to span a two-dimensional subspace with a non-degenerate metric.
+    #[inline]
+    pub fn sudakov_decompose(
+        &self,
+        n1: &LorentzVector<T>,
+        n2: &LorentzVector<T>,
+    ) -> (T, T, LorentzVector<T>) {
+        let (pn1, pn2) = (self.dot(n1), self.dot(n2));
+        let (n11, n12, n22) = (n1.square(), n1.dot(n2), n2.square());
+        let inv_det = (n11 * n22 - n12 * n12).inv();
+        let a = (pn1 * n22 - pn2 * n12) * inv_det;
+        let b = (pn2 * n11 - pn1 * n12) * inv_det;
+        (a, b, *self - n1 * a - n2 * b)
+    }
+
+    /// Apply a parity transformation to all momenta.
+    pub fn apply_parity(momenta: &mut [LorentzVector<T>]) {
+        for p in momenta {
+            *p = p.parity();
+        }
+    }
+
+    /// Apply time reversal to all position vectors.
+    pub fn apply_time_reversal(momenta: &mut [LorentzVector<T>]) {
+        for p in momenta {
+            *p = p.time_reversal();
+        }
+    }
+
+    /// Reflect all momenta across the plane with normal `normal`.
+    pub fn apply_reflection(momenta: &mut [LorentzVector<T>], normal: &LorentzVector<T>) {
+        for p in momenta {
+            *p = p.reflect(normal);
+        }
+    }
+
     #[inline]
     pub fn square(&self) -> T {
         self.t * self.t - self.x * self.x - self.y * self.y - self.z * self.z
     }
 
+    /// Compute the invariant mass squared. This is the same as `square()`.
+    #[inline]
+    pub fn m2(&self) -> T {
+        self.square()
+    }
+
     #[inline]
     pub fn dot(&self, other: &LorentzVector<T>) -> T {
         self.t * other.t - self.x * other.x - self.y * other.y - self.z * other.z
     }
 
+    /// Compute the squared length of the spatial part. Comparing it to the square of a
+    /// threshold avoids the square root of `spatial_distance()` in cuts.
     #[inline]
     pub fn spatial_squared(&self) -> T {
         self.x * self.x + self.y * self.y + self.z * self.z
     }
 
+    /// Compute the transverse momentum squared. Comparing it to the square of a
+    /// threshold avoids the square root of `pt()` in cuts.
+    #[inline]
+    pub fn pt2(&self) -> T {
+        self.x * self.x + self.y * self.y
+    }
+
     #[inline]
     pub fn spatial_dot(&self, other: &LorentzVector<T>) -> T {
         self.x * other.x + self.y * other.y + self.z * other.z
@@ -247,6 +504,34 @@ impl<T: Field> LorentzVector<T> {
         }
     }
 
+    /// Combine the components of two vectors pairwise.
+    #[inline]
+    pub fn map2<F, U: Field, V: Field>(&self, other: &LorentzVector<U>, map: F) -> LorentzVector<V>
+    where
+        F: Fn(T, U) -> V,
+    {
+        LorentzVector {
+            t: map(self.t, other.t),
+            x: map(self.x, other.x),
+            y: map(self.y, other.y),
+            z: map(self.z, other.z),
+        }
+    }
+
+    /// Map the components, together with their index `0..4` starting with the energy.
+    #[inline]
+    pub fn map_with_index<F, U: Field>(&self, map: F) -> LorentzVector<U>
+    where
+        F: Fn(usize, T) -> U,
+    {
+        LorentzVector {
+            t: map(0, self.t),
+            x: map(1, self.x),
+            y: map(2, self.y),
+            z: map(3, self.z),
+        }
+    }
+
     #[inline]
     pub fn from<U: Field + Into<T>>(a: LorentzVector<U>) -> Self {
         LorentzVector {
@@ -267,6 +552,8 @@ impl<T: Field> LorentzVector<T> {
         }
     }
 
+    /// Add `sign * other`, where the sign is -1, 0 or 1.
+    /// This panics for other signs; use `checked_add_signed()` for a fallible version.
     #[inline]
     pub fn add_signed(&mut self, other: &LorentzVector<T>, sign: i8) -> LorentzVector<T> {
         match sign {
@@ -276,9 +563,51 @@ impl<T: Field> LorentzVector<T> {
             _ => unreachable!("Sign is not -1,0,1"),
         }
     }
+
+    /// Add `sign * other`, or return `None` if the sign is not -1, 0 or 1.
+    #[inline]
+    pub fn checked_add_signed(
+        &self,
+        other: &LorentzVector<T>,
+        sign: i8,
+    ) -> Option<LorentzVector<T>> {
+        match sign {
+            0 => Some(*self),
+            1 => Some(*self + other),
+            -1 => Some(*self - other),
+            _ => None,
+        }
+    }
+
+    /// Get component `index`, in the order `t, x, y, z`, or `None` if the index is
+    /// larger than 3.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match index {
+            0 => Some(&self.t),
+            1 => Some(&self.x),
+            2 => Some(&self.y),
+            3 => Some(&self.z),
+            _ => None,
+        }
+    }
+
+    /// Get a mutable reference to component `index`, or `None` if the index is larger than 3.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match index {
+            0 => Some(&mut self.t),
+            1 => Some(&mut self.x),
+            2 => Some(&mut self.y),
+            3 => Some(&mut self.z),
+            _ => None,
+        }
+    }
 }
 
 impl<T: Field + ToPrimitive> LorentzVector<T> {
+    /// Cast the vector to another precision.
+    /// This panics if a component cannot be represented; use `try_cast()` for a fallible version.
     #[inline]
     pub fn cast<U: Field + NumCast>(&self) -> LorentzVector<U> {
         LorentzVector {
@@ -288,29 +617,128 @@ impl<T: Field + ToPrimitive> LorentzVector<T> {
             z: <U as NumCast>::from(self.z).unwrap(),
         }
     }
+
+    /// Cast the vector to another precision, checking that every component can be represented.
+    #[inline]
+    pub fn try_cast<U: Field + NumCast>(&self) -> Result<LorentzVector<U>, Error> {
+        let cast = |c: T, component: usize| {
+            <U as NumCast>::from(c).ok_or(Error::Unrepresentable { component })
+        };
+        Ok(LorentzVector {
+            t: cast(self.t, 0)?,
+            x: cast(self.x, 1)?,
+            y: cast(self.y, 2)?,
+            z: cast(self.z, 3)?,
+        })
+    }
+
+    /// Lazily cast a set of momenta to another precision.
+    #[inline]
+    pub fn cast_iter<'a, U: Field + NumCast + 'a>(
+        momenta: &'a [LorentzVector<T>],
+    ) -> impl Iterator<Item = LorentzVector<U>> + 'a {
+        momenta.iter().map(|p| p.cast())
+    }
+
+    /// Cast a set of momenta to another precision.
+    pub fn cast_slice<U: Field + NumCast>(momenta: &[LorentzVector<T>]) -> Vec<LorentzVector<U>> {
+        momenta.iter().map(|p| p.cast()).collect()
+    }
+
+    /// Cast a set of momenta to another precision, in parallel for large sets.
+    #[cfg(feature = "rayon_support")]
+    pub fn par_cast_slice<U: Field + NumCast + Send>(
+        momenta: &[LorentzVector<T>],
+    ) -> Vec<LorentzVector<U>>
+    where
+        T: Sync,
+    {
+        use rayon::prelude::*;
+        momenta
+            .par_iter()
+            .with_min_len(1024)
+            .map(|p| p.cast())
+            .collect()
+    }
+}
+
+/// Compute `a * b + c`. The operation is fused, unless the `deterministic` feature
+/// is enabled, in which case the result is the same on platforms without FMA.
+///
+/// With `deterministic`, the arithmetic of the crate is free of FMA, and the sums
+/// (`Sum`, `dot()`, `invariant_mass()` and `invariant_mass_iter()`) are evaluated strictly
+/// from left to right, so that results of additions and multiplications are bit-identical
+/// across platforms. This does not extend to methods that call `sin`, `cos`, `atan2`, `ln`
+/// or other functions of the platform's math library, such as rotations, `phi()`,
+/// `pseudo_rap()` and `rapidity()`.
+#[inline]
+fn fma<T: Field + MulAdd<Output = T>>(a: T, b: T, c: T) -> T {
+    #[cfg(feature = "deterministic")]
+    {
+        a * b + c
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        a.mul_add(b, c)
+    }
+}
+
+/// Compute `a * b + c` for a `Float`, see `fma`.
+#[inline]
+fn float_fma<T: Float>(a: T, b: T, c: T) -> T {
+    #[cfg(feature = "deterministic")]
+    {
+        a * b + c
+    }
+    #[cfg(not(feature = "deterministic"))]
+    {
+        a.mul_add(b, c)
+    }
 }
 
 impl<T: Field + MulAdd<Output = T>> LorentzVector<T> {
     #[inline]
     pub fn square_impr(&self) -> T {
-        self.t.mul_add(self.t, -self.spatial_squared_impr())
+        fma(self.t, self.t, -self.spatial_squared_impr())
     }
 
     #[inline]
     pub fn spatial_squared_impr(&self) -> T {
-        self.x
-            .mul_add(self.x, self.y.mul_add(self.y, self.z * self.z))
+        fma(self.x, self.x, fma(self.y, self.y, self.z * self.z))
     }
 
     #[inline]
     pub fn dot_impr(&self, other: &LorentzVector<T>) -> T {
-        self.t.mul_add(other.t, -self.spatial_dot_impr(other))
+        fma(self.t, other.t, -self.spatial_dot_impr(other))
     }
 
     #[inline]
     pub fn spatial_dot_impr(&self, other: &LorentzVector<T>) -> T {
-        self.x
-            .mul_add(other.x, self.y.mul_add(other.y, self.z * other.z))
+        fma(self.x, other.x, fma(self.y, other.y, self.z * other.z))
+    }
+}
+
+impl<T: Field + MulAdd<Output = T>> MulAdd<T, LorentzVector<T>> for LorentzVector<T> {
+    type Output = LorentzVector<T>;
+
+    /// Compute `self * a + b` with a fused multiply-add per component, or with a
+    /// separate multiplication and addition if the `deterministic` feature is enabled.
+    #[inline]
+    fn mul_add(self, a: T, b: LorentzVector<T>) -> LorentzVector<T> {
+        LorentzVector {
+            t: fma(self.t, a, b.t),
+            x: fma(self.x, a, b.x),
+            y: fma(self.y, a, b.y),
+            z: fma(self.z, a, b.z),
+        }
+    }
+}
+
+impl<T: Field + MulAdd<Output = T>> MulAddAssign<T, LorentzVector<T>> for LorentzVector<T> {
+    /// Compute `self = self * a + b`, see `mul_add()`.
+    #[inline]
+    fn mul_add_assign(&mut self, a: T, b: LorentzVector<T>) {
+        *self = self.mul_add(a, b);
     }
 }
 
@@ -554,87 +982,225 @@ impl<T: Field> Inv for LorentzVector<T> {
     }
 }
 
-impl<'a, T: RealNumberLike> Sub<&'a LorentzVector<T>> for &'a LorentzVector<Complex<T>> {
+/// Implement the component-wise operator `$op` between complex and real vectors,
+/// for all combinations of owned and borrowed operands and with either vector on
+/// the left, as well as the assigning operator on a complex vector.
+macro_rules! impl_complex_real_op {
+    ($op:ident, $f:ident, $op_assign:ident, $f_assign:ident, $sym:tt) => {
+        impl<'a, T: RealNumberLike> $op<&'a LorentzVector<T>> for &'a LorentzVector<Complex<T>> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: &'a LorentzVector<T>) -> LorentzVector<Complex<T>> {
+                LorentzVector {
+                    t: self.t $sym other.t,
+                    x: self.x $sym other.x,
+                    y: self.y $sym other.y,
+                    z: self.z $sym other.z,
+                }
+            }
+        }
+
+        impl<'a, T: RealNumberLike> $op<LorentzVector<T>> for &'a LorentzVector<Complex<T>> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: LorentzVector<T>) -> LorentzVector<Complex<T>> {
+                self $sym &other
+            }
+        }
+
+        impl<'a, T: RealNumberLike> $op<&'a LorentzVector<T>> for LorentzVector<Complex<T>> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: &'a LorentzVector<T>) -> LorentzVector<Complex<T>> {
+                &self $sym other
+            }
+        }
+
+        impl<T: RealNumberLike> $op<LorentzVector<T>> for LorentzVector<Complex<T>> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: LorentzVector<T>) -> LorentzVector<Complex<T>> {
+                &self $sym &other
+            }
+        }
+
+        impl<'a, T: RealNumberLike> $op<&'a LorentzVector<Complex<T>>> for &'a LorentzVector<T> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: &'a LorentzVector<Complex<T>>) -> LorentzVector<Complex<T>> {
+                LorentzVector {
+                    t: Complex::new(self.t, T::zero()) $sym other.t,
+                    x: Complex::new(self.x, T::zero()) $sym other.x,
+                    y: Complex::new(self.y, T::zero()) $sym other.y,
+                    z: Complex::new(self.z, T::zero()) $sym other.z,
+                }
+            }
+        }
+
+        impl<'a, T: RealNumberLike> $op<LorentzVector<Complex<T>>> for &'a LorentzVector<T> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: LorentzVector<Complex<T>>) -> LorentzVector<Complex<T>> {
+                self $sym &other
+            }
+        }
+
+        impl<'a, T: RealNumberLike> $op<&'a LorentzVector<Complex<T>>> for LorentzVector<T> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: &'a LorentzVector<Complex<T>>) -> LorentzVector<Complex<T>> {
+                &self $sym other
+            }
+        }
+
+        impl<T: RealNumberLike> $op<LorentzVector<Complex<T>>> for LorentzVector<T> {
+            type Output = LorentzVector<Complex<T>>;
+
+            #[inline]
+            fn $f(self, other: LorentzVector<Complex<T>>) -> LorentzVector<Complex<T>> {
+                &self $sym &other
+            }
+        }
+
+        impl<'a, T: RealNumberLike> $op_assign<&'a LorentzVector<T>> for LorentzVector<Complex<T>> {
+            #[inline]
+            fn $f_assign(&mut self, other: &'a LorentzVector<T>) {
+                *self = &*self $sym other;
+            }
+        }
+
+        impl<T: RealNumberLike> $op_assign<LorentzVector<T>> for LorentzVector<Complex<T>> {
+            #[inline]
+            fn $f_assign(&mut self, other: LorentzVector<T>) {
+                *self = &*self $sym &other;
+            }
+        }
+    };
+}
+
+impl_complex_real_op!(Add, add, AddAssign, add_assign, +);
+impl_complex_real_op!(Sub, sub, SubAssign, sub_assign, -);
+
+impl<'a, T: RealNumberLike> Mul<T> for &'a LorentzVector<Complex<T>> {
     type Output = LorentzVector<Complex<T>>;
 
     #[inline]
-    fn sub(self, other: &'a LorentzVector<T>) -> LorentzVector<Complex<T>> {
+    fn mul(self, other: T) -> LorentzVector<Complex<T>> {
         LorentzVector {
-            t: self.t - other.t,
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
+            t: self.t * other,
+            x: self.x * other,
+            y: self.y * other,
+            z: self.z * other,
         }
     }
 }
 
-impl<'a, T: RealNumberLike> Sub<&'a LorentzVector<T>> for LorentzVector<Complex<T>> {
+impl<T: RealNumberLike> Mul<T> for LorentzVector<Complex<T>> {
     type Output = LorentzVector<Complex<T>>;
 
     #[inline]
-    fn sub(self, other: &'a LorentzVector<T>) -> LorentzVector<Complex<T>> {
-        LorentzVector {
-            t: self.t - other.t,
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
-        }
+    fn mul(self, other: T) -> LorentzVector<Complex<T>> {
+        &self * other
     }
 }
 
-impl<T: RealNumberLike> Sub<LorentzVector<T>> for LorentzVector<Complex<T>> {
+impl<'a, T: RealNumberLike> Div<T> for &'a LorentzVector<Complex<T>> {
     type Output = LorentzVector<Complex<T>>;
 
     #[inline]
-    fn sub(self, other: LorentzVector<T>) -> LorentzVector<Complex<T>> {
-        LorentzVector {
-            t: self.t - other.t,
-            x: self.x - other.x,
-            y: self.y - other.y,
-            z: self.z - other.z,
-        }
+    fn div(self, other: T) -> LorentzVector<Complex<T>> {
+        self * other.inv()
     }
 }
 
-impl<'a, T: RealNumberLike> Add<&'a LorentzVector<T>> for &'a LorentzVector<Complex<T>> {
+impl<T: RealNumberLike> Div<T> for LorentzVector<Complex<T>> {
     type Output = LorentzVector<Complex<T>>;
 
     #[inline]
-    fn add(self, other: &'a LorentzVector<T>) -> LorentzVector<Complex<T>> {
-        LorentzVector {
-            t: self.t + other.t,
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
+    fn div(self, other: T) -> LorentzVector<Complex<T>> {
+        &self * other.inv()
     }
 }
 
-impl<'a, T: RealNumberLike> Add<&'a LorentzVector<T>> for LorentzVector<Complex<T>> {
+impl<T: RealNumberLike> MulAssign<T> for LorentzVector<Complex<T>> {
+    #[inline]
+    fn mul_assign(&mut self, other: T) {
+        self.t *= other;
+        self.x *= other;
+        self.y *= other;
+        self.z *= other;
+    }
+}
+
+impl<'a, T: RealNumberLike> Mul<Complex<T>> for &'a LorentzVector<T> {
     type Output = LorentzVector<Complex<T>>;
 
     #[inline]
-    fn add(self, other: &'a LorentzVector<T>) -> LorentzVector<Complex<T>> {
+    fn mul(self, other: Complex<T>) -> LorentzVector<Complex<T>> {
         LorentzVector {
-            t: self.t + other.t,
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
+            t: other * self.t,
+            x: other * self.x,
+            y: other * self.y,
+            z: other * self.z,
         }
     }
 }
 
-impl<T: RealNumberLike> Add<LorentzVector<T>> for LorentzVector<Complex<T>> {
+impl<T: RealNumberLike> Mul<Complex<T>> for LorentzVector<T> {
     type Output = LorentzVector<Complex<T>>;
 
     #[inline]
-    fn add(self, other: LorentzVector<T>) -> LorentzVector<Complex<T>> {
-        LorentzVector {
-            t: self.t + other.t,
-            x: self.x + other.x,
-            y: self.y + other.y,
-            z: self.z + other.z,
-        }
+    fn mul(self, other: Complex<T>) -> LorentzVector<Complex<T>> {
+        &self * other
+    }
+}
+
+/// Implement multiplication of complex vectors by a real scalar on the left.
+/// The orphan rules only allow this for concrete scalar types.
+macro_rules! impl_left_real_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> Mul<&'a LorentzVector<Complex<$t>>> for $t {
+                type Output = LorentzVector<Complex<$t>>;
+
+                #[inline]
+                fn mul(self, other: &'a LorentzVector<Complex<$t>>) -> LorentzVector<Complex<$t>> {
+                    other * self
+                }
+            }
+
+            impl Mul<LorentzVector<Complex<$t>>> for $t {
+                type Output = LorentzVector<Complex<$t>>;
+
+                #[inline]
+                fn mul(self, other: LorentzVector<Complex<$t>>) -> LorentzVector<Complex<$t>> {
+                    &other * self
+                }
+            }
+        )*
+    };
+}
+
+impl_left_real_scalar_mul!(f32, f64);
+
+/// The vectors are summed strictly in the order of the iterator.
+impl<T: Field> Sum<LorentzVector<T>> for LorentzVector<T> {
+    fn sum<I: Iterator<Item = LorentzVector<T>>>(iter: I) -> LorentzVector<T> {
+        iter.fold(LorentzVector::new(), |acc, v| acc + v)
+    }
+}
+
+/// The vectors are summed strictly in the order of the iterator.
+impl<'a, T: Field> Sum<&'a LorentzVector<T>> for LorentzVector<T> {
+    fn sum<I: Iterator<Item = &'a LorentzVector<T>>>(iter: I) -> LorentzVector<T> {
+        iter.fold(LorentzVector::new(), |acc, v| acc + v)
     }
 }
 
@@ -790,6 +1356,7 @@ impl<T: RealField> SubAssign<LorentzVector<f64>> for LorentzVector<T> {
     }
 }
 
+/// Indexing panics for an index larger than 3; use `get()` for a fallible version.
 impl<T: Field> Index<usize> for LorentzVector<T> {
     type Output = T;
 
@@ -818,7 +1385,96 @@ impl<T: Field> IndexMut<usize> for LorentzVector<T> {
     }
 }
 
+/// Compute pi in the precision of `T`, instead of rounding the `f64` constant.
+#[inline]
+fn pi<T: Float>() -> T {
+    (-T::one()).acos()
+}
+
+/// Compute `1 / sqrt(2)` in the precision of `T`.
+#[inline]
+fn frac_1_sqrt_2<T: Float>() -> T {
+    T::from(0.5).unwrap().sqrt()
+}
+
+/// Wrap an angle to the range `(-pi, pi]`.
+#[inline]
+fn wrap_phi<T: Float>(phi: T) -> T {
+    let pi = pi::<T>();
+    let two_pi = pi + pi;
+    let phi = phi % two_pi;
+    if phi > pi {
+        phi - two_pi
+    } else if phi <= -pi {
+        phi + two_pi
+    } else {
+        phi
+    }
+}
+
 impl<T: Float + Field> LorentzVector<T> {
+    /// Create a vector from the transverse momentum, pseudorapidity, azimuthal angle and mass.
+    #[inline]
+    pub fn from_pt_eta_phi_m(pt: T, eta: T, phi: T, m: T) -> LorentzVector<T> {
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        LorentzVector::from_args(
+            (pt * eta.cosh()).hypot(m),
+            pt * cos_phi,
+            pt * sin_phi,
+            pt * eta.sinh(),
+        )
+    }
+
+    /// Create a vector from the transverse momentum, pseudorapidity, azimuthal angle and energy.
+    #[inline]
+    pub fn from_pt_eta_phi_e(pt: T, eta: T, phi: T, e: T) -> LorentzVector<T> {
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        LorentzVector::from_args(e, pt * cos_phi, pt * sin_phi, pt * eta.sinh())
+    }
+
+    /// Create an on-shell vector with mass `m` from its spatial components.
+    #[inline]
+    pub fn from_spatial_and_mass(px: T, py: T, pz: T, m: T) -> LorentzVector<T> {
+        LorentzVector::from_args((px * px + py * py + pz * pz + m * m).sqrt(), px, py, pz)
+    }
+
+    /// Create an on-shell vector with mass `m` from the spatial part of `p`.
+    #[inline]
+    pub fn from_spatial_vector_and_mass(p: &LorentzVector<T>, m: T) -> LorentzVector<T> {
+        LorentzVector::from_spatial_and_mass(p.x, p.y, p.z, m)
+    }
+
+    /// Create a vector from its light-cone components `p+` and `p-` and its transverse
+    /// components.
+    #[inline]
+    pub fn from_lightcone(
+        p_plus: T,
+        p_minus: T,
+        px: T,
+        py: T,
+        convention: LightConeConvention,
+    ) -> LorentzVector<T> {
+        let norm = match convention {
+            LightConeConvention::Unnormalized => T::from(0.5).unwrap(),
+            LightConeConvention::Symmetric => frac_1_sqrt_2::<T>(),
+        };
+        LorentzVector::from_args((p_plus + p_minus) * norm, px, py, (p_plus - p_minus) * norm)
+    }
+
+    /// Create a vector from the energy, the magnitude of the spatial momentum, the polar
+    /// angle with respect to the z-axis and the azimuthal angle.
+    #[inline]
+    pub fn from_spherical(e: T, p: T, theta: T, phi: T) -> LorentzVector<T> {
+        let (sin_theta, cos_theta) = theta.sin_cos();
+        let (sin_phi, cos_phi) = phi.sin_cos();
+        LorentzVector::from_args(
+            e,
+            p * sin_theta * cos_phi,
+            p * sin_theta * sin_phi,
+            p * cos_theta,
+        )
+    }
+
     #[inline]
     pub fn spatial_distance(&self) -> T {
         (self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
@@ -830,33 +1486,509 @@ impl<T: Float + Field> LorentzVector<T> {
     }
 
     pub fn boost(&self, boost_vector: &LorentzVector<T>) -> LorentzVector<T> {
+        let (gamma, gamma2) = LorentzVector::boost_factors(boost_vector.spatial_squared());
+        let r = self.boost_with_factors(boost_vector, gamma, gamma2);
+        consistency_check!(
+            r.square() - self.square(),
+            r.euclidean_distance() * self.euclidean_distance(),
+            "boost does not preserve the square"
+        );
+        r
+    }
+
+    /// Boost with a velocity `|beta| << 1`, using a series expansion of `(gamma - 1) / beta^2`
+    /// instead of computing `gamma - 1` directly. The truncation error is of order `beta^12`.
+    pub fn boost_small(&self, boost_vector: &LorentzVector<T>) -> LorentzVector<T> {
         let b2 = boost_vector.spatial_squared();
         let gamma = (T::one() - b2).sqrt().inv();
+        self.boost_with_factors(boost_vector, gamma, LorentzVector::small_boost_gamma2(b2))
+    }
 
-        let bp = self.spatial_dot(boost_vector);
-        let gamma2 = if b2 > T::zero() {
-            (gamma - T::one()) / b2
+    /// The series expansion of `(gamma - 1) / beta^2` in `beta^2`.
+    #[inline]
+    fn small_boost_gamma2(b2: T) -> T {
+        let c = |n: f64, d: f64| T::from(n / d).unwrap();
+        c(1., 2.)
+            + b2 * (c(3., 8.)
+                + b2 * (c(5., 16.)
+                    + b2 * (c(35., 128.) + b2 * (c(63., 256.) + b2 * c(231., 1024.)))))
+    }
+
+    /// Compute `gamma` and `(gamma - 1) / beta^2` of a boost with velocity squared `b2`.
+    #[inline]
+    pub(crate) fn boost_factors(b2: T) -> (T, T) {
+        let gamma = (T::one() - b2).sqrt().inv();
+        // below this velocity the series of boost_small is accurate to machine precision
+        // and avoids the cancellation in gamma - 1
+        if b2 < T::epsilon().sqrt().cbrt() {
+            (gamma, LorentzVector::small_boost_gamma2(b2))
         } else {
-            T::zero()
-        };
+            (gamma, (gamma - T::one()) / b2)
+        }
+    }
+
+    /// Boost along the z-axis by `rapidity`, which is added to the rapidity of the vector.
+    /// This is the same as `boost()` with the velocity `tanh(rapidity)` along the z-axis,
+    /// but remains accurate for large rapidities, where the velocity rounds to 1.
+    #[inline]
+    pub fn boost_z(&self, rapidity: T) -> LorentzVector<T> {
+        // scale the light-cone components, which avoids the cancellation between
+        // t cosh(y) and z sinh(y)
+        let half = T::from(0.5).unwrap();
+        let plus = (self.t + self.z) * rapidity.exp() * half;
+        let minus = (self.t - self.z) * (-rapidity).exp() * half;
+        LorentzVector::from_args(plus + minus, self.x, self.y, plus - minus)
+    }
+
+    /// Rotate the spatial part counter-clockwise by `angle` around the x-axis.
+    #[inline]
+    pub fn rotate_x(&self, angle: T) -> LorentzVector<T> {
+        let (sin, cos) = angle.sin_cos();
+        let r = LorentzVector::from_args(
+            self.t,
+            self.x,
+            self.y * cos - self.z * sin,
+            self.y * sin + self.z * cos,
+        );
+        consistency_check!(
+            r.spatial_squared() - self.spatial_squared(),
+            self.spatial_squared(),
+            "rotation does not preserve the spatial norm"
+        );
+        r
+    }
+
+    /// Rotate the spatial part counter-clockwise by `angle` around the y-axis.
+    #[inline]
+    pub fn rotate_y(&self, angle: T) -> LorentzVector<T> {
+        let (sin, cos) = angle.sin_cos();
+        let r = LorentzVector::from_args(
+            self.t,
+            self.x * cos + self.z * sin,
+            self.y,
+            self.z * cos - self.x * sin,
+        );
+        consistency_check!(
+            r.spatial_squared() - self.spatial_squared(),
+            self.spatial_squared(),
+            "rotation does not preserve the spatial norm"
+        );
+        r
+    }
+
+    /// Rotate the spatial part counter-clockwise by `angle` around the z-axis.
+    #[inline]
+    pub fn rotate_z(&self, angle: T) -> LorentzVector<T> {
+        let (sin, cos) = angle.sin_cos();
+        let r = LorentzVector::from_args(
+            self.t,
+            self.x * cos - self.y * sin,
+            self.x * sin + self.y * cos,
+            self.z,
+        );
+        consistency_check!(
+            r.spatial_squared() - self.spatial_squared(),
+            self.spatial_squared(),
+            "rotation does not preserve the spatial norm"
+        );
+        r
+    }
+
+    /// Rotate the spatial part counter-clockwise by `angle` around the spatial part of `axis`,
+    /// using Rodrigues' formula. The axis does not have to be normalized. A vanishing axis
+    /// has no direction, and the vector is returned unchanged.
+    #[inline]
+    pub fn rotate_axis_angle(&self, axis: &LorentzVector<T>, angle: T) -> LorentzVector<T> {
+        let norm = axis.spatial_distance();
+        if norm == T::zero() {
+            return *self;
+        }
+        let inv_norm = norm.inv();
+        let (kx, ky, kz) = (axis.x * inv_norm, axis.y * inv_norm, axis.z * inv_norm);
+        let (sin, cos) = angle.sin_cos();
+        let f = (kx * self.x + ky * self.y + kz * self.z) * (T::one() - cos);
+        let r = LorentzVector::from_args(
+            self.t,
+            self.x * cos + (ky * self.z - kz * self.y) * sin + kx * f,
+            self.y * cos + (kz * self.x - kx * self.z) * sin + ky * f,
+            self.z * cos + (kx * self.y - ky * self.x) * sin + kz * f,
+        );
+        consistency_check!(
+            r.spatial_squared() - self.spatial_squared(),
+            self.spatial_squared(),
+            "rotation does not preserve the spatial norm"
+        );
+        r
+    }
+
+    /// Rotate the spatial part with the rotation that maps the spatial part of `reference`
+    /// onto the positive z-axis, along the shortest arc. Applying this to every momentum of
+    /// an event aligns the event with `reference`, for example to define a helicity frame.
+    #[inline]
+    pub fn rotate_to_z_axis(&self, reference: &LorentzVector<T>) -> LorentzVector<T> {
+        if reference.pt2() == T::zero() {
+            return if reference.z < T::zero() {
+                self.rotate_x(pi())
+            } else {
+                *self
+            };
+        }
+
+        let axis = LorentzVector::from_args(T::zero(), reference.y, -reference.x, T::zero());
+        self.rotate_axis_angle(&axis, reference.theta())
+    }
+
+    #[inline]
+    fn boost_with_factors(
+        &self,
+        boost_vector: &LorentzVector<T>,
+        gamma: T,
+        gamma2: T,
+    ) -> LorentzVector<T> {
+        let bp = self.spatial_dot(boost_vector);
         let factor = gamma2 * bp + gamma * self.t;
         LorentzVector::from_args(
             gamma * (self.t + bp),
-            boost_vector.x.mul_add(factor, self.x),
-            boost_vector.y.mul_add(factor, self.y),
-            boost_vector.z.mul_add(factor, self.z),
+            float_fma(boost_vector.x, factor, self.x),
+            float_fma(boost_vector.y, factor, self.y),
+            float_fma(boost_vector.z, factor, self.z),
         )
     }
 
+    /// Check if the vector lies inside or on the forward light cone.
+    #[inline]
+    pub fn is_in_forward_light_cone(&self) -> bool {
+        self.t >= self.spatial_distance()
+    }
+
+    /// Check if the vector lies inside or on the backward light cone.
+    #[inline]
+    pub fn is_in_backward_light_cone(&self) -> bool {
+        -self.t >= self.spatial_distance()
+    }
+
+    /// Check if the event at this position can causally influence the event at `other`,
+    /// i.e. if `other` lies inside or on the forward light cone of this event.
+    #[inline]
+    pub fn can_causally_influence(&self, other: &LorentzVector<T>) -> bool {
+        (other - self).is_in_forward_light_cone()
+    }
+
+    /// Compare two events in the causal partial order. An event is smaller than
+    /// the events it can causally influence. Spacelike separated events are not comparable.
+    pub fn causal_cmp(&self, other: &LorentzVector<T>) -> Option<std::cmp::Ordering> {
+        let d = other - self;
+        if d.t == T::zero() && d.spatial_squared() == T::zero() {
+            Some(std::cmp::Ordering::Equal)
+        } else if d.is_in_forward_light_cone() {
+            Some(std::cmp::Ordering::Less)
+        } else if d.is_in_backward_light_cone() {
+            Some(std::cmp::Ordering::Greater)
+        } else {
+            None
+        }
+    }
+
+    /// Boost with `boost_vector`, returning an error instead of NaNs when the
+    /// velocity is not smaller than the speed of light.
+    pub fn try_boost(&self, boost_vector: &LorentzVector<T>) -> Result<LorentzVector<T>, Error> {
+        let b2 = boost_vector.spatial_squared();
+        if b2 >= T::one() || b2.is_nan() {
+            return Err(Error::Superluminal {
+                beta_squared: b2.to_f64().unwrap_or(f64::NAN),
+            });
+        }
+        Ok(self.boost(boost_vector))
+    }
+
+    /// Restore the invariant mass of a vector that drifted numerically, for example
+    /// after a long chain of transformations. A timelike vector is rescaled as a whole,
+    /// so that its direction in Minkowski space is kept. Otherwise, or for a massless
+    /// reference, the energy is recomputed from the spatial momentum.
+    pub fn renormalize_against(&self, reference_mass: T) -> LorentzVector<T> {
+        let m2 = self.square();
+        if reference_mass > T::zero() && m2 > T::zero() {
+            let m = m2.sqrt();
+            #[cfg(feature = "tracing_support")]
+            {
+                if (m - reference_mass).abs() > T::epsilon().sqrt() * reference_mass {
+                    instability!(
+                        mass = ?m,
+                        reference_mass = ?reference_mass,
+                        "mass renormalization beyond tolerance"
+                    );
+                }
+            }
+            return self * (reference_mass / m);
+        }
+
+        self.with_mass(reference_mass)
+    }
+
+    /// Put the vector on the mass shell of mass `m`, keeping the spatial momentum and
+    /// the sign of the energy.
+    #[inline]
+    pub fn with_mass(&self, m: T) -> LorentzVector<T> {
+        let mut p = *self;
+        p.set_energy_on_shell(m);
+        p
+    }
+
+    /// Recompute the energy in place such that the vector has mass `m`, keeping the
+    /// sign of the energy.
+    #[inline]
+    pub fn set_energy_on_shell(&mut self, m: T) {
+        let e = (self.spatial_squared() + m * m).sqrt();
+        #[cfg(feature = "tracing_support")]
+        {
+            if (e - self.t.abs()).abs() > T::epsilon().sqrt() * e {
+                instability!(
+                    energy = ?self.t,
+                    on_shell_energy = ?e,
+                    "on-shell projection beyond tolerance"
+                );
+            }
+        }
+        self.t = if self.t < T::zero() { -e } else { e };
+    }
+
+    /// Scale the energy by `factor`, keeping the direction of the spatial momentum.
+    /// With `MassPolicy::Preserve` the magnitude of the spatial momentum is recomputed
+    /// from the mass; if the scaled energy drops below the mass, the vector is put at rest.
+    pub fn scale_energy(&self, factor: T, policy: MassPolicy) -> LorentzVector<T> {
+        match policy {
+            MassPolicy::Scale => self * factor,
+            MassPolicy::Preserve => {
+                let e = self.t * factor;
+                let p2 = e * e - self.square();
+                let p_abs = self.spatial_distance();
+                if p2 <= T::zero() || p_abs == T::zero() {
+                    LorentzVector::from_args(e, T::zero(), T::zero(), T::zero())
+                } else {
+                    let r = p2.sqrt() / p_abs;
+                    LorentzVector::from_args(e, self.x * r, self.y * r, self.z * r)
+                }
+            }
+        }
+    }
+
+    /// Scale the spatial momentum by `factor`. With `MassPolicy::Preserve` the energy
+    /// is recomputed from the mass, keeping its sign.
+    pub fn scale_momentum(&self, factor: T, policy: MassPolicy) -> LorentzVector<T> {
+        match policy {
+            MassPolicy::Scale => self * factor,
+            MassPolicy::Preserve => {
+                let m2 = self.square();
+                let p = LorentzVector::from_args(
+                    T::zero(),
+                    self.x * factor,
+                    self.y * factor,
+                    self.z * factor,
+                );
+                let e2 = p.spatial_squared() + m2;
+                let e = if e2 > T::zero() { e2.sqrt() } else { T::zero() };
+                LorentzVector::from_args(if self.t < T::zero() { -e } else { e }, p.x, p.y, p.z)
+            }
+        }
+    }
+
+    /// Create the up and down variations of a set of momenta, with energies
+    /// scaled by `1 + shift` and `1 - shift` respectively.
+    pub fn energy_scale_variations(
+        momenta: &[LorentzVector<T>],
+        shift: T,
+        policy: MassPolicy,
+    ) -> (Vec<LorentzVector<T>>, Vec<LorentzVector<T>>) {
+        let up = momenta
+            .iter()
+            .map(|p| p.scale_energy(T::one() + shift, policy))
+            .collect();
+        let down = momenta
+            .iter()
+            .map(|p| p.scale_energy(T::one() - shift, policy))
+            .collect();
+        (up, down)
+    }
+
+    /// Create the up and down variations of a set of momenta, with spatial momenta
+    /// scaled by `1 + shift` and `1 - shift` respectively.
+    pub fn momentum_scale_variations(
+        momenta: &[LorentzVector<T>],
+        shift: T,
+        policy: MassPolicy,
+    ) -> (Vec<LorentzVector<T>>, Vec<LorentzVector<T>>) {
+        let up = momenta
+            .iter()
+            .map(|p| p.scale_momentum(T::one() + shift, policy))
+            .collect();
+        let down = momenta
+            .iter()
+            .map(|p| p.scale_momentum(T::one() - shift, policy))
+            .collect();
+        (up, down)
+    }
+
+    /// Compute the invariant mass. For spacelike momenta, which can arise from
+    /// rounding errors in nearly massless momenta, `-sqrt(-m2)` is returned instead of NaN.
+    #[inline]
+    pub fn m(&self) -> T {
+        let m2 = self.m2();
+        if m2 < T::zero() {
+            -(-m2).sqrt()
+        } else {
+            m2.sqrt()
+        }
+    }
+
+    /// Compute the light-cone component `p+`.
+    #[inline]
+    pub fn plus(&self, convention: LightConeConvention) -> T {
+        match convention {
+            LightConeConvention::Unnormalized => self.t + self.z,
+            LightConeConvention::Symmetric => (self.t + self.z) * frac_1_sqrt_2::<T>(),
+        }
+    }
+
+    /// Compute the light-cone component `p-`.
+    #[inline]
+    pub fn minus(&self, convention: LightConeConvention) -> T {
+        match convention {
+            LightConeConvention::Unnormalized => self.t - self.z,
+            LightConeConvention::Symmetric => (self.t - self.z) * frac_1_sqrt_2::<T>(),
+        }
+    }
+
+    /// Compute the transverse mass squared `E^2 - pz^2`.
+    #[inline]
+    pub fn mt2(&self) -> T {
+        (self.t - self.z) * (self.t + self.z)
+    }
+
+    /// Compute the transverse mass, with the same sign convention as `m()`.
+    #[inline]
+    pub fn mt(&self) -> T {
+        let mt2 = self.mt2();
+        if mt2 < T::zero() {
+            -(-mt2).sqrt()
+        } else {
+            mt2.sqrt()
+        }
+    }
+
     /// Compute transverse momentum.
     #[inline]
     pub fn pt(&self) -> T {
-        (self.x * self.x + self.y * self.y).sqrt()
+        self.pt2().sqrt()
+    }
+
+    /// Compute the velocity `|p| / E`.
+    #[inline]
+    pub fn beta(&self) -> T {
+        self.spatial_distance() / self.t
+    }
+
+    /// Compute the velocity vector `p / E`, with a vanishing time component.
+    /// `v.boost(&p.beta_vector())` boosts `v` from the rest frame of `p` to the
+    /// current frame, and `v.boost_to_rest_frame_of(&p)` boosts it into the rest frame.
+    #[inline]
+    pub fn beta_vector(&self) -> LorentzVector<T> {
+        let inv_e = self.t.inv();
+        LorentzVector::from_args(T::zero(), self.x * inv_e, self.y * inv_e, self.z * inv_e)
+    }
+
+    /// Compute the boost vector `-p / E` that boosts into the rest frame of this vector
+    /// with `boost()`.
+    #[inline]
+    pub fn restframe_boost_vector(&self) -> LorentzVector<T> {
+        -self.beta_vector()
+    }
+
+    /// Boost into the rest frame of `p`.
+    #[inline]
+    pub fn boost_to_rest_frame_of(&self, p: &LorentzVector<T>) -> LorentzVector<T> {
+        self.boost(&p.restframe_boost_vector())
     }
 
-    /// Compute pseudorapidity.
+    /// Compute the Lorentz factor `E / m`. Massless momenta yield infinity.
+    #[inline]
+    pub fn gamma(&self) -> T {
+        let m2 = self.square();
+        if m2 <= T::zero() {
+            T::infinity()
+        } else {
+            self.t / m2.sqrt()
+        }
+    }
+
+    /// Compute the Lorentz factor `1 / sqrt(1 - beta^2)` of the velocity `beta`. It is
+    /// evaluated as `1 / sqrt((1 - beta) (1 + beta))`, which is more accurate for
+    /// ultra-relativistic velocities.
+    #[inline]
+    pub fn gamma_from_beta(beta: T) -> T {
+        ((T::one() - beta) * (T::one() + beta)).sqrt().inv()
+    }
+
+    /// Compute the transverse energy `E * pt / |p|`. It vanishes for a vanishing
+    /// spatial momentum, and is equal to `pt` for massless momenta.
+    #[inline]
+    pub fn et(&self) -> T {
+        let p = self.spatial_distance();
+        if p == T::zero() {
+            T::zero()
+        } else {
+            self.t * (self.pt() / p)
+        }
+    }
+
+    /// Compute the polar angle with respect to the z-axis, in `[0, pi]`.
+    /// A vanishing spatial momentum yields 0, consistent with `cos_theta()`.
+    #[inline]
+    pub fn theta(&self) -> T {
+        if self.x == T::zero() && self.y == T::zero() && self.z == T::zero() {
+            // atan2 would yield pi for a negative zero z-component
+            return T::zero();
+        }
+        self.pt().atan2(self.z)
+    }
+
+    /// Compute the cosine of the polar angle. A vanishing spatial momentum yields 1.
+    #[inline]
+    pub fn cos_theta(&self) -> T {
+        let p = self.spatial_distance();
+        if p == T::zero() {
+            T::one()
+        } else {
+            self.z / p
+        }
+    }
+
+    /// Compute pseudorapidity `0.5 * ln((|p| + pz) / (|p| - pz))`. It is evaluated as
+    /// `sign(pz) * ln((|p| + |pz|) / pt)`, which has no cancellations for forward momenta.
+    /// Momenta on the beam axis yield `T::max_value()` or `T::min_value()`.
     #[inline]
     pub fn pseudo_rap(&self) -> T {
+        let pt = self.pt();
+        if pt == T::zero() {
+            return if self.z > T::zero() {
+                T::max_value()
+            } else {
+                T::min_value()
+            };
+        }
+
+        let pz = self.z.abs();
+        let eta = ((self.spatial_distance() + pz) / pt).ln();
+        if self.z < T::zero() {
+            -eta
+        } else {
+            eta
+        }
+    }
+
+    /// Compute pseudorapidity with the `-ln(tan(theta / 2))` formula that was used
+    /// by `pseudo_rap()` before, for comparison.
+    #[inline]
+    pub fn pseudo_rap_atan2(&self) -> T {
         let pt = self.pt();
         if pt < T::epsilon() && self.z.abs() < T::epsilon() {
             if self.z > T::zero() {
@@ -870,25 +2002,145 @@ impl<T: Float + Field> LorentzVector<T> {
     }
 
     /// Compute the phi-angle separation with p2.
+    /// A cosine that exceeds 1 by more than the machine epsilon causes a panic; use
+    /// `checked_getdelphi()` for a fallible version.
     pub fn getdelphi(&self, p2: &LorentzVector<T>) -> T {
+        match self.checked_getdelphi(p2) {
+            Some(delta_phi) => delta_phi,
+            None => panic!("Cosine larger than 1. in phase-space cuts."),
+        }
+    }
+
+    /// Compute the phi-angle separation with p2, or `None` if the cosine exceeds 1 by more
+    /// than the machine epsilon.
+    pub fn checked_getdelphi(&self, p2: &LorentzVector<T>) -> Option<T> {
         let pt1 = self.pt();
         let pt2 = p2.pt();
         if pt1 == T::zero() || pt2 == T::zero() {
-            return T::max_value();
+            return Some(T::max_value());
         }
 
         let mut tmp = self.x * p2.x + self.y * p2.y;
         tmp = tmp / (pt1 * pt2);
         if tmp.abs() > T::one() + T::epsilon() {
-            panic!("Cosine larger than 1. in phase-space cuts.")
+            return None;
         }
         if tmp.abs() > T::one() {
-            (tmp / tmp.abs()).acos()
+            instability!(cosine = ?tmp, "clamped cosine in getdelphi");
+            Some((tmp / tmp.abs()).acos())
         } else {
-            tmp.acos()
+            Some(tmp.acos())
         }
     }
 
+    /// Compute the opening angle between the spatial parts, in `[0, pi]`.
+    /// It is evaluated as `atan2(|p1 x p2|, p1.p2)`, which needs no clamp of the cosine and
+    /// remains accurate for small angles. A vanishing spatial momentum yields 0.
+    #[inline]
+    pub fn spatial_angle(&self, p2: &LorentzVector<T>) -> T {
+        let cx = self.y * p2.z - self.z * p2.y;
+        let cy = self.z * p2.x - self.x * p2.z;
+        let cz = self.x * p2.y - self.y * p2.x;
+        (cx * cx + cy * cy + cz * cz)
+            .sqrt()
+            .atan2(self.spatial_dot(p2))
+    }
+
+    /// Compute the signed azimuthal separation `phi - phi_2`, wrapped to `(-pi, pi]`.
+    #[inline]
+    pub fn delta_phi(&self, p2: &LorentzVector<T>) -> T {
+        let cross = self.y * p2.x - self.x * p2.y;
+        let dot = self.x * p2.x + self.y * p2.y;
+        wrap_phi(cross.atan2(dot))
+    }
+
+    /// Compute the azimuthal angle in `(-pi, pi]`.
+    #[inline]
+    pub fn phi(&self) -> T {
+        wrap_phi(self.y.atan2(self.x))
+    }
+
+    /// Compute the azimuthal angle in `[0, 2 pi)`.
+    #[inline]
+    pub fn phi_0_2pi(&self) -> T {
+        let two_pi = pi::<T>() + pi::<T>();
+        let phi = self.y.atan2(self.x);
+        if phi >= T::zero() {
+            phi
+        } else if phi + two_pi < two_pi {
+            phi + two_pi
+        } else {
+            T::zero()
+        }
+    }
+
+    /// Compute the rapidity `0.5 * ln((E + pz) / (E - pz))`. Vectors with `E <= |pz|`,
+    /// such as massless momenta along the beam axis, yield `T::max_value()` or `T::min_value()`.
+    #[inline]
+    pub fn rapidity(&self) -> T {
+        let pz = self.z.abs();
+        if self.t <= pz {
+            return if self.z > T::zero() {
+                T::max_value()
+            } else {
+                T::min_value()
+            };
+        }
+        let y = ((self.t + pz) / (self.t - pz)).ln() / (T::one() + T::one());
+        if self.z < T::zero() {
+            -y
+        } else {
+            y
+        }
+    }
+
+    /// Compute the rapidity difference `y - y2`.
+    #[inline]
+    pub fn delta_rap(&self, p2: &LorentzVector<T>) -> T {
+        self.rapidity() - p2.rapidity()
+    }
+
+    /// Check if the vector lies within a cone of radius `radius` around `axis`,
+    /// with distances measured in the rapidity-azimuth plane.
+    #[inline]
+    pub fn is_within_cone(&self, axis: &LorentzVector<T>, radius: T) -> bool {
+        let delta_y = self.delta_rap(axis);
+        let delta_phi = self.delta_phi(axis);
+        delta_y * delta_y + delta_phi * delta_phi <= radius * radius
+    }
+
+    /// Sum all momenta within a cone of radius `radius` around `axis`.
+    pub fn cone_sum(
+        momenta: &[LorentzVector<T>],
+        axis: &LorentzVector<T>,
+        radius: T,
+    ) -> LorentzVector<T> {
+        let mut sum = LorentzVector::new();
+        for p in momenta {
+            if p.is_within_cone(axis, radius) {
+                sum += p;
+            }
+        }
+        sum
+    }
+
+    /// Compute the invariant mass of the system of all momenta, with the sign
+    /// convention of `m()`.
+    #[inline]
+    pub fn invariant_mass(momenta: &[LorentzVector<T>]) -> T {
+        momenta.iter().sum::<LorentzVector<T>>().m()
+    }
+
+    /// Compute the invariant mass of the system of the momenta of an iterator.
+    /// The momenta are summed in the order of the iterator.
+    #[inline]
+    pub fn invariant_mass_iter<'a, I: IntoIterator<Item = &'a LorentzVector<T>>>(momenta: I) -> T
+    where
+        T: 'a,
+    {
+        momenta.into_iter().sum::<LorentzVector<T>>().m()
+    }
+
     /// Compute the deltaR separation with momentum p2.
     #[inline]
     pub fn delta_r(&self, p2: &LorentzVector<T>) -> T {
@@ -902,82 +2154,167 @@ impl<T: Float + Field> LorentzVector<T> {
     ///
     /// * `p` - Starting Lorentz vector to define the boost.
     /// * `q` - Target Lorentz vector to define the boost.
+    ///
+    /// To boost many vectors from `p` to `q`, construct a `BoostFromTo` once instead.
+    #[inline]
     pub fn boost_from_to(&self, p: &LorentzVector<T>, q: &LorentzVector<T>) -> LorentzVector<T> {
-        let eps = T::epsilon() + T::epsilon();
-        let p_abs = p.euclidean_distance();
-        let q_abs = q.euclidean_distance();
-
-        if (p - q).spatial_distance() < eps * eps {
-            return LorentzVector::new();
-        }
-
-        let mut n_vec = q - p;
-        n_vec = n_vec / n_vec.spatial_distance();
-
-        let na = LorentzVector::from_args(T::one(), n_vec.x, n_vec.y, n_vec.z);
-        let nb = LorentzVector::from_args(T::one(), -n_vec.x, -n_vec.y, -n_vec.z);
-
-        let p_plus = p.dot(&nb);
-        let p_minus = p.dot(&na);
-        let q_plus = q.dot(&nb);
-        let q_minus = q.dot(&na);
-        let ratioa;
-        let ratiob;
-        if p_minus / p_abs < eps && q_minus / q_abs < eps {
-            if p_plus / p_abs < eps && q_plus / q_abs < eps {
-                ratioa = T::one();
-                ratiob = T::one();
-            } else {
-                ratiob = q_plus / p_plus;
-                ratioa = T::one() / ratiob;
-            }
-        } else {
-            if p_plus / p_abs < eps && q_plus / q_abs < eps {
-                ratioa = q_minus / p_minus;
-                ratiob = T::one() / ratioa;
-            } else {
-                ratioa = q_minus / p_minus;
-                ratiob = q_plus / p_plus;
-            }
-        }
-
-        let plus = self.dot(&nb);
-        let minus = self.dot(&na);
-
-        self + na * (ratiob - T::one()) / (T::one() + T::one()) * plus
-            + nb * (ratioa - T::one()) / (T::one() + T::one()) * minus
+        BoostFromTo::new(p, q).apply(self)
     }
-}
 
-impl LorentzVector<f64> {
     /// Boost this kinematic configuration from the center of mass frame to the lab frame
     /// given specified Bjorken x's x1 and x2.
     /// This function needs to be cleaned up and built in a smarter way as the boost vector can be written
     /// down explicitly as a function of x1, x2 and the beam energies.
     pub fn boost_from_com_to_lab_frame(
-        momenta: &mut [LorentzVector<f64>],
-        x1: f64,
-        x2: f64,
-        ebeam1: f64,
-        ebeam2: f64,
+        momenta: &mut [LorentzVector<T>],
+        x1: T,
+        x2: T,
+        ebeam1: T,
+        ebeam2: T,
     ) {
-        let target_summed =
-            LorentzVector::from_args(x1 * ebeam1, 0., 0., (x1 * ebeam1).copysign(momenta[0].z))
-                + LorentzVector::from_args(
-                    x2 * ebeam2,
-                    0.,
-                    0.,
-                    (x2 * ebeam2).copysign(momenta[1].z),
-                );
-
-        let source_summed =
-            LorentzVector::from_args(2. * (x1 * x2 * ebeam1 * ebeam2).sqrt(), 0., 0., 0.);
+        let copysign = |a: T, b: T| if b.is_sign_negative() { -a } else { a };
+
+        let target_summed = LorentzVector::from_args(
+            x1 * ebeam1,
+            T::zero(),
+            T::zero(),
+            copysign(x1 * ebeam1, momenta[0].z),
+        ) + LorentzVector::from_args(
+            x2 * ebeam2,
+            T::zero(),
+            T::zero(),
+            copysign(x2 * ebeam2, momenta[1].z),
+        );
+
+        let source_summed = LorentzVector::from_args(
+            (T::one() + T::one()) * (x1 * x2 * ebeam1 * ebeam2).sqrt(),
+            T::zero(),
+            T::zero(),
+            T::zero(),
+        );
 
         // We want to send the source to the target
+        let boost = BoostFromTo::new(&source_summed, &target_summed);
         for vec in momenta {
-            *vec = vec.boost_from_to(&source_summed, &target_summed);
+            *vec = boost.apply(vec);
         }
     }
+
+    /// Boost all momenta in place with velocity `beta`, computing the Lorentz factor
+    /// only once.
+    pub fn boost_slice(momenta: &mut [LorentzVector<T>], beta: &LorentzVector<T>) {
+        let (gamma, gamma2) = LorentzVector::boost_factors(beta.spatial_squared());
+        for vec in momenta {
+            *vec = vec.boost_with_factors(beta, gamma, gamma2);
+        }
+    }
+}
+
+#[cfg(feature = "f128_support")]
+impl<T: Field + ToPrimitive> LorentzVector<T> {
+    /// Convert to quadruple precision.
+    #[inline]
+    pub fn to_f128(&self) -> LorentzVector<f128::f128> {
+        self.cast()
+    }
+
+    /// Convert a set of momenta to quadruple precision.
+    pub fn slice_to_f128(momenta: &[LorentzVector<T>]) -> Vec<LorentzVector<f128::f128>> {
+        momenta.iter().map(|p| p.to_f128()).collect()
+    }
+}
+
+#[cfg(feature = "f128_support")]
+impl LorentzVector<f128::f128> {
+    /// Convert to double precision, rounding every component.
+    #[inline]
+    pub fn to_f64_lossy(&self) -> LorentzVector<f64> {
+        self.cast()
+    }
+
+    /// Convert a set of momenta to double precision, rounding every component.
+    pub fn slice_to_f64_lossy(momenta: &[LorentzVector<f128::f128>]) -> Vec<LorentzVector<f64>> {
+        momenta.iter().map(|p| p.to_f64_lossy()).collect()
+    }
+}
+
+/// Approximate `atan2(y, x)` with a polynomial, with a maximum error of 2e-5.
+#[inline]
+fn fast_atan2(y: f64, x: f64) -> f64 {
+    let (ax, ay) = (x.abs(), y.abs());
+    if ax == 0. && ay == 0. {
+        return 0.;
+    }
+
+    let a = ax.min(ay) / ax.max(ay);
+    let s = a * a;
+    let mut r =
+        a * (0.9998660 + s * (-0.3302995 + s * (0.1801410 + s * (-0.0851330 + s * 0.0208351))));
+    if ay > ax {
+        r = std::f64::consts::FRAC_PI_2 - r;
+    }
+    if x < 0. {
+        r = std::f64::consts::PI - r;
+    }
+    if y < 0. {
+        -r
+    } else {
+        r
+    }
+}
+
+/// Approximate the natural logarithm of a positive normal number, using its binary
+/// exponent and a short series for the mantissa. The maximum error is 1e-9.
+#[inline]
+fn fast_ln(x: f64) -> f64 {
+    let bits = x.to_bits();
+    let mut e = ((bits >> 52) & 0x7ff) as i64 - 1023;
+    let mut m = f64::from_bits((bits & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000);
+    if m > std::f64::consts::SQRT_2 {
+        m *= 0.5;
+        e += 1;
+    }
+
+    let s = (m - 1.) / (m + 1.);
+    let s2 = s * s;
+    e as f64 * std::f64::consts::LN_2
+        + 2. * s * (1. + s2 * (1. / 3. + s2 * (1. / 5. + s2 * (1. / 7. + s2 * (1. / 9.)))))
+}
+
+impl LorentzVector<f64> {
+    /// Compute the pseudorapidity using a fast logarithm approximation.
+    /// The absolute error is below 1e-7.
+    #[inline]
+    pub fn fast_eta(&self) -> f64 {
+        let pt2 = self.x * self.x + self.y * self.y;
+        if pt2 == 0. {
+            return if self.z > 0. { f64::MAX } else { f64::MIN };
+        }
+
+        let pz = self.z.abs();
+        let eta = fast_ln(((pt2 + pz * pz).sqrt() + pz) / pt2.sqrt());
+        if self.z < 0. {
+            -eta
+        } else {
+            eta
+        }
+    }
+
+    /// Compute the azimuthal angle using a polynomial approximation of `atan2`.
+    /// The absolute error is below 2e-5.
+    #[inline]
+    pub fn fast_phi(&self) -> f64 {
+        fast_atan2(self.y, self.x)
+    }
+
+    /// Compute the deltaR separation with `p2` from `fast_eta()` and `fast_phi()`.
+    /// The absolute error is below 3e-5.
+    #[inline]
+    pub fn fast_delta_r(&self, p2: &LorentzVector<f64>) -> f64 {
+        let delta_eta = self.fast_eta() - p2.fast_eta();
+        let delta_phi = wrap_phi(self.fast_phi() - p2.fast_phi());
+        (delta_eta * delta_eta + delta_phi * delta_phi).sqrt()
+    }
 }
 
 impl<T: RealNumberLike> LorentzVector<T> {
@@ -1034,4 +2371,122 @@ where
     pub fn real(&self) -> LorentzVector<T> {
         self.map(|x| x.real())
     }
+
+    /// Compute the Minkowski product with `other`, processing the real parts and the dual
+    /// parts in separate loops over plain numbers instead of multiplying duals. The result
+    /// is the same as that of `dot()`, up to the order of the rounding.
+    pub fn dot_split(&self, other: &LorentzVector<DualN<T, U>>) -> DualN<T, U> {
+        let a = [&self.t, &self.x, &self.y, &self.z];
+        let b = [&other.t, &other.x, &other.y, &other.z];
+        let (ra, rb) = (self.real(), other.real());
+
+        let mut r = DualN::from_real(ra.dot(&rb));
+        for k in 1..U::dim() {
+            r[k] = rb.t * a[0][k] + ra.t * b[0][k]
+                - rb.x * a[1][k]
+                - ra.x * b[1][k]
+                - rb.y * a[2][k]
+                - ra.y * b[2][k]
+                - rb.z * a[3][k]
+                - ra.z * b[3][k];
+        }
+        r
+    }
+
+    /// Compute the square with separate loops for the real and dual parts, see `dot_split()`.
+    pub fn square_split(&self) -> DualN<T, U> {
+        let a = [&self.t, &self.x, &self.y, &self.z];
+        let ra = self.real();
+        let two = T::one() + T::one();
+
+        let mut r = DualN::from_real(ra.square());
+        for k in 1..U::dim() {
+            r[k] = two * (ra.t * a[0][k] - ra.x * a[1][k] - ra.y * a[2][k] - ra.z * a[3][k]);
+        }
+        r
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants_in_precision() {
+        assert_eq!(pi::<f64>(), std::f64::consts::PI);
+        assert_eq!(pi::<f32>(), std::f32::consts::PI);
+        assert_eq!(frac_1_sqrt_2::<f64>(), std::f64::consts::FRAC_1_SQRT_2);
+        assert_eq!(frac_1_sqrt_2::<f32>(), std::f32::consts::FRAC_1_SQRT_2);
+        assert_eq!(
+            LorentzVector::from_args(1f64, -1., 0., 0.).phi(),
+            std::f64::consts::PI
+        );
+    }
+
+    #[test]
+    fn reflect() {
+        let p = LorentzVector::from_args(4f64, 1., 2., 3.);
+        let r = p.reflect(&LorentzVector::from_args(7., 0., 0., 2.));
+        assert_eq!((r.t, r.x, r.y, r.z), (4., 1., 2., -3.));
+        let r = p.reflect(&LorentzVector::new());
+        assert_eq!((r.t, r.x, r.y, r.z), (p.t, p.x, p.y, p.z));
+    }
+
+    #[test]
+    fn zero_axis() {
+        let p = LorentzVector::from_args(4f64, 1., 2., 3.);
+        let zero = LorentzVector::new();
+
+        let (par, perp) = p.decompose_along(&zero);
+        assert_eq!((par.t, par.x, par.y, par.z), (0., 0., 0., 0.));
+        assert_eq!((perp.t, perp.x, perp.y, perp.z), (0., 1., 2., 3.));
+
+        let r = p.rotate_axis_angle(&zero, 1.);
+        assert_eq!((r.t, r.x, r.y, r.z), (p.t, p.x, p.y, p.z));
+        assert_eq!(
+            Rotation::from_axis_angle(&zero, 1.).quaternion(),
+            (1., 0., 0., 0.)
+        );
+    }
+
+    #[test]
+    fn theta_of_zero_momentum() {
+        for p in &[
+            LorentzVector::from_args(1f64, 0., 0., 0.),
+            -LorentzVector::new(),
+        ] {
+            assert_eq!(p.theta(), 0.);
+            assert_eq!(p.cos_theta(), 1.);
+        }
+        assert_eq!(
+            LorentzVector::from_args(1f64, 0., 0., -1.).theta(),
+            std::f64::consts::PI
+        );
+    }
+
+    #[test]
+    fn fast_ln_error() {
+        for i in 0..=100_000 {
+            let m = 1. + f64::from(i) / 100_000.;
+            for scale in &[1., 1e-300, 0.03125, 3.7e200] {
+                let x = m * scale;
+                assert!((fast_ln(x) - x.ln()).abs() < 1e-9, "x = {}", x);
+            }
+        }
+    }
+
+    #[test]
+    fn fast_atan2_error() {
+        for i in 0..100_000 {
+            let angle = std::f64::consts::PI * (2. * f64::from(i) / 100_000. - 1.);
+            for r in &[1e-5, 1., 1e8] {
+                let (y, x) = (r * angle.sin(), r * angle.cos());
+                assert!(
+                    (fast_atan2(y, x) - y.atan2(x)).abs() < 2e-5,
+                    "angle = {}",
+                    angle
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,221 @@
+use crate::Error;
+use num::Float;
+
+/// Check that `a` is a dense `n x n` matrix.
+fn check_square<T>(a: &[T], n: usize) -> Result<(), Error> {
+    if a.len() != n * n {
+        return Err(Error::BadSliceLength {
+            expected: n * n,
+            found: a.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Solve the linear system `a x = b` for a dense row-major `n x n` matrix, using
+/// Gaussian elimination with partial pivoting. `a` is overwritten and the solution
+/// is stored in `b`.
+pub fn solve<T: Float>(a: &mut [T], b: &mut [T], n: usize) -> Result<(), Error> {
+    check_square(a, n)?;
+    if b.len() != n {
+        return Err(Error::BadSliceLength {
+            expected: n,
+            found: b.len(),
+        });
+    }
+
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|i, j| {
+                a[i * n + col]
+                    .abs()
+                    .partial_cmp(&a[j * n + col].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        if a[pivot * n + col] == T::zero() || a[pivot * n + col].is_nan() {
+            return Err(Error::SingularMatrix);
+        }
+
+        if pivot != col {
+            for k in 0..n {
+                a.swap(pivot * n + k, col * n + k);
+            }
+            b.swap(pivot, col);
+        }
+
+        let inv_pivot = a[col * n + col].recip();
+        for row in col + 1..n {
+            let f = a[row * n + col] * inv_pivot;
+            if f == T::zero() {
+                continue;
+            }
+            for k in col..n {
+                a[row * n + k] = a[row * n + k] - f * a[col * n + k];
+            }
+            b[row] = b[row] - f * b[col];
+        }
+    }
+
+    for row in (0..n).rev() {
+        let mut s = b[row];
+        for k in row + 1..n {
+            s = s - a[row * n + k] * b[k];
+        }
+        b[row] = s / a[row * n + row];
+    }
+    Ok(())
+}
+
+/// Compute the determinant of a dense row-major `n x n` matrix, using Gaussian elimination
+/// with partial pivoting.
+pub fn determinant<T: Float>(a: &[T], n: usize) -> Result<T, Error> {
+    check_square(a, n)?;
+
+    let mut a = a.to_vec();
+    let mut det = T::one();
+    for col in 0..n {
+        let pivot = (col..n)
+            .max_by(|i, j| {
+                a[i * n + col]
+                    .abs()
+                    .partial_cmp(&a[j * n + col].abs())
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .unwrap();
+        if a[pivot * n + col] == T::zero() {
+            return Ok(T::zero());
+        }
+
+        if pivot != col {
+            for k in 0..n {
+                a.swap(pivot * n + k, col * n + k);
+            }
+            det = -det;
+        }
+
+        det = det * a[col * n + col];
+        let inv_pivot = a[col * n + col].recip();
+        for row in col + 1..n {
+            let f = a[row * n + col] * inv_pivot;
+            for k in col..n {
+                a[row * n + k] = a[row * n + k] - f * a[col * n + k];
+            }
+        }
+    }
+    Ok(det)
+}
+
+/// Compute the eigenvalues and eigenvectors of a dense row-major symmetric `n x n` matrix,
+/// such as a sphericity tensor, using the cyclic Jacobi method.
+/// The eigenvalues are sorted in decreasing order, and row `i` of the returned row-major
+/// matrix is the normalized eigenvector of eigenvalue `i`. Only the upper triangle of `a`
+/// is read.
+pub fn symmetric_eigen<T: Float>(a: &[T], n: usize) -> Result<(Vec<T>, Vec<T>), Error> {
+    check_square(a, n)?;
+
+    let mut m = vec![T::zero(); n * n];
+    let mut v = vec![T::zero(); n * n];
+    for i in 0..n {
+        for j in i..n {
+            m[i * n + j] = a[i * n + j];
+            m[j * n + i] = a[i * n + j];
+        }
+        v[i * n + i] = T::one();
+    }
+
+    let norm = m.iter().fold(T::zero(), |acc, x| acc + *x * *x);
+    for _ in 0..50 {
+        let mut off_diagonal = T::zero();
+        for p in 0..n {
+            for q in p + 1..n {
+                off_diagonal = off_diagonal + m[p * n + q] * m[p * n + q];
+            }
+        }
+        if off_diagonal <= T::epsilon() * T::epsilon() * norm {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                if m[p * n + q] == T::zero() {
+                    continue;
+                }
+
+                // the rotation that eliminates element (p, q)
+                let theta = (m[q * n + q] - m[p * n + p]) / (m[p * n + q] + m[p * n + q]);
+                let t = theta.signum() / (theta.abs() + theta.hypot(T::one()));
+                let c = t.hypot(T::one()).recip();
+                let s = t * c;
+
+                for k in 0..n {
+                    let (kp, kq) = (m[k * n + p], m[k * n + q]);
+                    m[k * n + p] = c * kp - s * kq;
+                    m[k * n + q] = s * kp + c * kq;
+                }
+                for k in 0..n {
+                    let (pk, qk) = (m[p * n + k], m[q * n + k]);
+                    m[p * n + k] = c * pk - s * qk;
+                    m[q * n + k] = s * pk + c * qk;
+                }
+                for k in 0..n {
+                    let (kp, kq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * kp - s * kq;
+                    v[k * n + q] = s * kp + c * kq;
+                }
+            }
+        }
+    }
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|i, j| {
+        m[j * n + j]
+            .partial_cmp(&m[i * n + i])
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    let eigenvalues = order.iter().map(|i| m[i * n + i]).collect();
+    let mut eigenvectors = vec![T::zero(); n * n];
+    for (row, i) in order.iter().enumerate() {
+        for k in 0..n {
+            eigenvectors[row * n + k] = v[k * n + i];
+        }
+    }
+    Ok((eigenvalues, eigenvectors))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimension_mismatch() {
+        let error = Error::BadSliceLength {
+            expected: 4,
+            found: 3,
+        };
+        let (mut a, mut b) = ([1., 2., 3.], [1., 2.]);
+        assert_eq!(solve(&mut a, &mut b, 2), Err(error.clone()));
+        assert_eq!(determinant(&a, 2), Err(error.clone()));
+        assert_eq!(symmetric_eigen(&a, 2), Err(error));
+    }
+
+    #[test]
+    fn small_matrices() {
+        let a = [2., 1., 1., 3.];
+        assert_eq!(determinant(&a, 2), Ok(5.));
+
+        let (mut m, mut b) = (a, [3., 5.]);
+        solve(&mut m, &mut b, 2).unwrap();
+        assert!((b[0] - 0.8).abs() < 1e-14 && (b[1] - 1.4).abs() < 1e-14);
+        assert_eq!(
+            solve(&mut [1., 2., 2., 4.], &mut [1., 1.], 2),
+            Err(Error::SingularMatrix)
+        );
+
+        let (values, vectors) = symmetric_eigen(&[2., 1., 1., 2.], 2).unwrap();
+        assert!((values[0] - 3.).abs() < 1e-14 && (values[1] - 1.).abs() < 1e-14);
+        assert!((vectors[0].abs() - 0.5f64.sqrt()).abs() < 1e-14);
+        assert!((vectors[0] - vectors[1]).abs() < 1e-14);
+    }
+}
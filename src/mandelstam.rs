@@ -0,0 +1,60 @@
+use crate::{Field, LorentzVector};
+use num::Float;
+
+/// Compute the Mandelstam variable `s = (p1 + p2)^2` of the scattering `p1 p2 -> p3 p4`.
+#[inline]
+pub fn s<T: Field>(p1: &LorentzVector<T>, p2: &LorentzVector<T>) -> T {
+    (p1 + p2).square()
+}
+
+/// Compute the Mandelstam variable `t = (p1 - p3)^2`.
+#[inline]
+pub fn t<T: Field>(p1: &LorentzVector<T>, p3: &LorentzVector<T>) -> T {
+    (p1 - p3).square()
+}
+
+/// Compute the Mandelstam variable `u = (p1 - p4)^2`.
+#[inline]
+pub fn u<T: Field>(p1: &LorentzVector<T>, p4: &LorentzVector<T>) -> T {
+    (p1 - p4).square()
+}
+
+/// Compute `s + t + u - (m1^2 + m2^2 + m3^2 + m4^2)` for the scattering `p1 p2 -> p3 p4`,
+/// which vanishes if momentum is conserved.
+#[inline]
+pub fn consistency_residual<T: Field>(
+    p1: &LorentzVector<T>,
+    p2: &LorentzVector<T>,
+    p3: &LorentzVector<T>,
+    p4: &LorentzVector<T>,
+) -> T {
+    s(p1, p2) + t(p1, p3) + u(p1, p4) - p1.square() - p2.square() - p3.square() - p4.square()
+}
+
+/// Check if `s + t + u` equals the sum of the masses squared, up to `tolerance`
+/// relative to `|s|`.
+#[inline]
+pub fn is_consistent<T: Float + Field>(
+    p1: &LorentzVector<T>,
+    p2: &LorentzVector<T>,
+    p3: &LorentzVector<T>,
+    p4: &LorentzVector<T>,
+    tolerance: T,
+) -> bool {
+    consistency_residual(p1, p2, p3, p4).abs() <= tolerance * s(p1, p2).abs()
+}
+
+/// Compute the Mandelstam variables `(s, t, u)` in the all-outgoing convention, where the
+/// incoming momenta are crossed to negative energy and the four momenta sum to zero:
+/// `s = (p1 + p2)^2`, `t = (p1 + p3)^2` and `u = (p1 + p4)^2`. The same expressions
+/// then hold in every crossed channel, with the physical channel determined by which
+/// momenta have a negative energy.
+#[inline]
+pub fn crossed_invariants<T: Field>(
+    p1: &LorentzVector<T>,
+    p2: &LorentzVector<T>,
+    p3: &LorentzVector<T>,
+    p4: &LorentzVector<T>,
+) -> (T, T, T) {
+    ((p1 + p2).square(), (p1 + p3).square(), (p1 + p4).square())
+}
@@ -0,0 +1,374 @@
+use crate::linalg;
+#[cfg(feature = "dual_num_support")]
+use crate::RealNumberLike;
+use crate::{Error, Field, LorentzVector};
+#[cfg(feature = "dual_num_support")]
+use dual_num::{Allocator, DefaultAllocator, Dim, DimName, DualN, Owned};
+use num::Float;
+#[cfg(feature = "dual_num_support")]
+use num::Signed;
+
+/// A Levenberg-Marquardt minimizer of a sum of squared residuals, for kinematic fits
+/// and other optimization problems in the momenta.
+/// The Jacobian is computed with forward finite differences, or exactly with dual numbers
+/// by `minimize_dual()` when the `dual_num_support` feature is enabled.
+#[derive(Debug, Copy, Clone)]
+pub struct LevenbergMarquardt<T: Float> {
+    /// The maximal number of iterations before `Error::NonConvergent` is returned.
+    pub max_iterations: usize,
+    /// The minimization stops when the relative step or the relative decrease of the
+    /// sum of squares drops below this tolerance.
+    pub tolerance: T,
+    /// The initial damping parameter.
+    pub damping: T,
+}
+
+impl<T: Float> Default for LevenbergMarquardt<T> {
+    fn default() -> LevenbergMarquardt<T> {
+        LevenbergMarquardt {
+            max_iterations: 200,
+            tolerance: T::epsilon().sqrt(),
+            damping: T::from(1e-3).unwrap(),
+        }
+    }
+}
+
+impl<T: Float> LevenbergMarquardt<T> {
+    pub fn new() -> LevenbergMarquardt<T> {
+        LevenbergMarquardt::default()
+    }
+
+    /// Compute the Jacobian of `f` at `x`, where the residuals are `r`.
+    fn jacobian<F: FnMut(&[T], &mut [T])>(
+        f: &mut F,
+        x: &mut [T],
+        r: &[T],
+        r_shifted: &mut [T],
+        jacobian: &mut [T],
+    ) {
+        let n = x.len();
+        let h0 = T::epsilon().sqrt();
+        for j in 0..n {
+            let xj = x[j];
+            let h = h0 * xj.abs().max(T::one());
+            x[j] = xj + h;
+            f(x, r_shifted);
+            // use the difference that is representable
+            let h = x[j] - xj;
+            x[j] = xj;
+            for i in 0..r.len() {
+                jacobian[i * n + j] = (r_shifted[i] - r[i]) / h;
+            }
+        }
+    }
+
+    /// Minimize the sum of squares of the `n_residuals` residuals computed by `f`, which
+    /// writes the residuals of the parameters in its first argument into its second argument.
+    /// `x` contains the starting point and is overwritten with the minimum.
+    /// Returns the Euclidean norm of the residuals at the minimum, or `Error::NonConvergent`
+    /// if the residuals at the starting point are not finite.
+    pub fn minimize<F: FnMut(&[T], &mut [T])>(
+        &self,
+        mut f: F,
+        n_residuals: usize,
+        x: &mut [T],
+    ) -> Result<T, Error> {
+        let mut x_shifted = x.to_vec();
+        let mut r_shifted = vec![T::zero(); n_residuals];
+        self.minimize_with_jacobian(
+            |x, r, jacobian| {
+                f(x, r);
+                if let Some(jacobian) = jacobian {
+                    x_shifted.copy_from_slice(x);
+                    LevenbergMarquardt::jacobian(
+                        &mut f,
+                        &mut x_shifted,
+                        r,
+                        &mut r_shifted,
+                        jacobian,
+                    );
+                }
+            },
+            n_residuals,
+            x,
+        )
+    }
+
+    /// Minimize the sum of squares of the residuals computed by `eval`, which writes the
+    /// residuals into its second argument and, if requested, the row-major Jacobian into
+    /// its third argument.
+    fn minimize_with_jacobian<F: FnMut(&[T], &mut [T], Option<&mut [T]>)>(
+        &self,
+        mut eval: F,
+        n_residuals: usize,
+        x: &mut [T],
+    ) -> Result<T, Error> {
+        let n = x.len();
+        let half = T::from(0.5).unwrap();
+        let ten = T::from(10.).unwrap();
+
+        let mut r = vec![T::zero(); n_residuals];
+        let mut r_trial = vec![T::zero(); n_residuals];
+        let mut jacobian = vec![T::zero(); n_residuals * n];
+        let mut jtj = vec![T::zero(); n * n];
+        let mut jtr = vec![T::zero(); n];
+        let mut a = vec![T::zero(); n * n];
+        let mut step = vec![T::zero(); n];
+        let mut x_trial = vec![T::zero(); n];
+
+        eval(x, &mut r, None);
+        let mut cost = half * r.iter().fold(T::zero(), |acc, ri| acc + *ri * *ri);
+        if !cost.is_finite() {
+            // accepted steps only decrease the cost, so it stays finite afterwards
+            return Err(Error::NonConvergent {
+                iterations: 0,
+                residual: (cost + cost).sqrt().to_f64().unwrap_or(f64::NAN),
+            });
+        }
+        let mut damping = self.damping;
+
+        for _ in 0..self.max_iterations {
+            eval(x, &mut r, Some(&mut jacobian[..]));
+
+            for i in 0..n {
+                jtr[i] =
+                    (0..n_residuals).fold(T::zero(), |acc, k| acc + jacobian[k * n + i] * r[k]);
+                for j in 0..=i {
+                    let s = (0..n_residuals).fold(T::zero(), |acc, k| {
+                        acc + jacobian[k * n + i] * jacobian[k * n + j]
+                    });
+                    jtj[i * n + j] = s;
+                    jtj[j * n + i] = s;
+                }
+            }
+
+            if cost == T::zero() || jtr.iter().all(|g| *g == T::zero()) {
+                return Ok((cost + cost).sqrt());
+            }
+
+            // increase the damping until a step decreases the sum of squares
+            loop {
+                a.copy_from_slice(&jtj);
+                for i in 0..n {
+                    let d = jtj[i * n + i];
+                    a[i * n + i] = d + damping * if d > T::zero() { d } else { T::one() };
+                    step[i] = -jtr[i];
+                }
+
+                if linalg::solve(&mut a, &mut step, n).is_ok() {
+                    for i in 0..n {
+                        x_trial[i] = x[i] + step[i];
+                    }
+                    eval(&x_trial, &mut r_trial, None);
+                    let trial_cost =
+                        half * r_trial.iter().fold(T::zero(), |acc, ri| acc + *ri * *ri);
+
+                    if trial_cost < cost {
+                        let x_norm = x.iter().fold(T::zero(), |acc, xi| acc.max(xi.abs()));
+                        let step_norm = step.iter().fold(T::zero(), |acc, s| acc.max(s.abs()));
+                        let decrease = cost - trial_cost;
+
+                        x.copy_from_slice(&x_trial);
+                        r.copy_from_slice(&r_trial);
+                        cost = trial_cost;
+                        damping = (damping / ten).max(T::epsilon());
+
+                        if step_norm <= self.tolerance * (x_norm + self.tolerance)
+                            || decrease <= self.tolerance * self.tolerance * cost
+                        {
+                            return Ok((cost + cost).sqrt());
+                        }
+                        break;
+                    }
+                }
+
+                damping = damping * ten;
+                if damping > T::epsilon().recip() {
+                    // no step decreases the sum of squares beyond rounding
+                    return Ok((cost + cost).sqrt());
+                }
+            }
+        }
+
+        Err(Error::NonConvergent {
+            iterations: self.max_iterations,
+            residual: (cost + cost).sqrt().to_f64().unwrap_or(f64::NAN),
+        })
+    }
+
+    /// Minimize the sum of squares of residuals that are functions of a set of momenta.
+    /// All components of `momenta` are parameters of the fit.
+    pub fn minimize_momenta<F: FnMut(&[LorentzVector<T>], &mut [T])>(
+        &self,
+        mut f: F,
+        n_residuals: usize,
+        momenta: &mut [LorentzVector<T>],
+    ) -> Result<T, Error>
+    where
+        T: Field,
+    {
+        let mut x: Vec<T> = momenta
+            .iter()
+            .flat_map(|p| vec![p.t, p.x, p.y, p.z])
+            .collect();
+        let mut buffer = momenta.to_vec();
+
+        let result = self.minimize(
+            |x, r| {
+                for (p, c) in buffer.iter_mut().zip(x.chunks(4)) {
+                    *p = LorentzVector::from_args(c[0], c[1], c[2], c[3]);
+                }
+                f(&buffer, r)
+            },
+            n_residuals,
+            &mut x,
+        );
+
+        for (p, c) in momenta.iter_mut().zip(x.chunks(4)) {
+            *p = LorentzVector::from_args(c[0], c[1], c[2], c[3]);
+        }
+        result
+    }
+}
+
+#[cfg(feature = "dual_num_support")]
+impl<T: RealNumberLike + Signed + 'static> LevenbergMarquardt<T> {
+    /// Minimize the sum of squares of the `n_residuals` residuals computed by `f`, like
+    /// `minimize()`, but with the Jacobian computed exactly with dual numbers instead of
+    /// finite differences. Every parameter is seeded with its own dual direction, so the
+    /// duals need at least `x.len() + 1` components; otherwise `Error::DualDimension`
+    /// is returned.
+    pub fn minimize_dual<U, F>(&self, f: F, n_residuals: usize, x: &mut [T]) -> Result<T, Error>
+    where
+        U: Dim + DimName,
+        DefaultAllocator: Allocator<T, U>,
+        Owned<T, U>: Copy,
+        F: Fn(&[DualN<T, U>], &mut [DualN<T, U>]),
+    {
+        let n = x.len();
+        if U::dim() < n + 1 {
+            return Err(Error::DualDimension {
+                required: n + 1,
+                found: U::dim(),
+            });
+        }
+
+        let mut x_dual = vec![DualN::from_real(T::zero()); n];
+        let mut r_dual = vec![DualN::from_real(T::zero()); n_residuals];
+        self.minimize_with_jacobian(
+            |x, r, jacobian| {
+                for (j, (d, xj)) in x_dual.iter_mut().zip(x).enumerate() {
+                    *d = DualN::from_real(*xj);
+                    d[j + 1] = T::one();
+                }
+                f(&x_dual, &mut r_dual);
+                for (ri, d) in r.iter_mut().zip(&r_dual) {
+                    *ri = d.real();
+                }
+                if let Some(jacobian) = jacobian {
+                    for (row, d) in jacobian.chunks_mut(n.max(1)).zip(&r_dual) {
+                        for (j, e) in row.iter_mut().enumerate() {
+                            *e = d[j + 1];
+                        }
+                    }
+                }
+            },
+            n_residuals,
+            x,
+        )
+    }
+
+    /// Minimize the sum of squares of residuals that are functions of a set of momenta,
+    /// like `minimize_momenta()`, but with the Jacobian computed with dual numbers. The
+    /// duals need at least `4 n + 1` components for `n` momenta.
+    pub fn minimize_momenta_dual<U, F>(
+        &self,
+        f: F,
+        n_residuals: usize,
+        momenta: &mut [LorentzVector<T>],
+    ) -> Result<T, Error>
+    where
+        U: Dim + DimName,
+        DefaultAllocator: Allocator<T, U>,
+        Owned<T, U>: Copy,
+        F: Fn(&[LorentzVector<DualN<T, U>>], &mut [DualN<T, U>]),
+    {
+        let mut x: Vec<T> = momenta
+            .iter()
+            .flat_map(|p| vec![p.t, p.x, p.y, p.z])
+            .collect();
+
+        let result = self.minimize_dual(
+            |x: &[DualN<T, U>], r: &mut [DualN<T, U>]| {
+                let buffer: Vec<LorentzVector<DualN<T, U>>> = x
+                    .chunks(4)
+                    .map(|c| LorentzVector::from_args(c[0], c[1], c[2], c[3]))
+                    .collect();
+                f(&buffer, r)
+            },
+            n_residuals,
+            &mut x,
+        );
+
+        for (p, c) in momenta.iter_mut().zip(x.chunks(4)) {
+            *p = LorentzVector::from_args(c[0], c[1], c[2], c[3]);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The residuals of a fit of a momentum to a measurement, with a mass constraint that
+    /// is weighted much stronger than the measurement.
+    fn mass_constrained<T: Field + Float>(p: &[LorentzVector<T>], r: &mut [T]) {
+        let measured = [10., 1., 2., 3.];
+        let (mass, weight) = (T::from(5.).unwrap(), T::from(1e3).unwrap());
+        r[0] = (p[0].square() - mass * mass) * weight;
+        for (mu, m) in measured.iter().enumerate() {
+            r[mu + 1] = p[0][mu] - T::from(*m).unwrap();
+        }
+    }
+
+    #[test]
+    fn mass_constrained_fit() {
+        let mut momenta = [LorentzVector::from_args(10f64, 1., 2., 3.)];
+        let residual = LevenbergMarquardt::new()
+            .minimize_momenta(mass_constrained::<f64>, 5, &mut momenta)
+            .unwrap();
+        assert!((momenta[0].m() - 5.).abs() < 1e-4);
+        // the fit moves the measurement towards the mass shell
+        assert!(residual > 0. && residual < 10.);
+    }
+
+    #[test]
+    fn non_finite_residuals() {
+        let mut x = [1f64, 2.];
+        let result = LevenbergMarquardt::new().minimize(|_, r| r[0] = f64::NAN, 1, &mut x);
+        match &result {
+            Err(Error::NonConvergent { iterations, .. }) => assert_eq!(*iterations, 0),
+            _ => panic!("NaN residuals reported as {:?}", result),
+        }
+    }
+
+    #[cfg(feature = "dual_num_support")]
+    #[test]
+    fn mass_constrained_fit_dual() {
+        use dual_num::U5;
+
+        let mut fd = [LorentzVector::from_args(10f64, 1., 2., 3.)];
+        let mut dual = fd;
+        let lm = LevenbergMarquardt::new();
+        let r_fd = lm
+            .minimize_momenta(mass_constrained::<f64>, 5, &mut fd)
+            .unwrap();
+        let r_dual = lm
+            .minimize_momenta_dual::<U5, _>(mass_constrained::<DualN<f64, U5>>, 5, &mut dual)
+            .unwrap();
+        assert!((dual[0].m() - 5.).abs() < 1e-4);
+        assert!((r_dual - r_fd).abs() < 1e-4 * r_fd);
+        assert!((dual[0] - fd[0]).euclidean_distance() < 1e-4);
+    }
+}
@@ -0,0 +1,166 @@
+use crate::kinematics::two_body_decay_momentum;
+use crate::{Field, LorentzVector};
+use num::Float;
+
+/// Create the spatial unit vector with polar angle `acos(cos_theta)` and azimuthal angle `phi`.
+#[inline]
+fn direction<T: Float + Field>(cos_theta: T, phi: T) -> LorentzVector<T> {
+    let sin_theta = ((T::one() - cos_theta) * (T::one() + cos_theta))
+        .max(T::zero())
+        .sqrt();
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    LorentzVector::from_args(
+        T::zero(),
+        sin_theta * cos_phi,
+        sin_theta * sin_phi,
+        cos_theta,
+    )
+}
+
+/// Decay `p` with mass `m` isotropically in its rest frame, with the direction of the first
+/// particle given by `r` in `[0, 1)^2`. Returns the momenta in the frame of `p` and the
+/// magnitude of the momentum in the rest frame. The mass is passed separately, so that
+/// rounding of `p` cannot move a system at the threshold below it.
+#[inline]
+fn two_body_decay<T: Float + Field>(
+    p: &LorentzVector<T>,
+    m: T,
+    m1: T,
+    m2: T,
+    r: [T; 2],
+) -> Option<([LorentzVector<T>; 2], T)> {
+    let q = two_body_decay_momentum(m, m1, m2)?;
+
+    let two = T::from(2.).unwrap();
+    let two_pi = T::from(2. * std::f64::consts::PI).unwrap();
+    let n = direction(two * r[0] - T::one(), two_pi * r[1]) * q;
+    let p1 = LorentzVector::from_args(q.hypot(m1), n.x, n.y, n.z);
+    let p2 = LorentzVector::from_args(q.hypot(m2), -n.x, -n.y, -n.z);
+
+    let boost_vector = p.beta_vector();
+    Some(([p1.boost(&boost_vector), p2.boost(&boost_vector)], q))
+}
+
+/// Map the point `r` in `[0, 1)^2` to the two-body phase space of a system with momentum
+/// `p` decaying into particles with masses `m1` and `m2`, with a uniform distribution of
+/// the solid angle in the rest frame of `p`.
+///
+/// The returned weight is the Jacobian `q / (4 pi m)`, where `q` is the momentum in the rest
+/// frame and `m` the mass of `p`, such that the average of the weight times a function
+/// estimates the integral over
+/// `dPhi_n = (2 pi)^4 delta^4(p - sum p_i) prod_i d^3p_i / ((2 pi)^3 2 E_i)`.
+/// Returns `None` if `p` is below the threshold.
+pub fn two_body_phase_space<T: Float + Field>(
+    p: &LorentzVector<T>,
+    m1: T,
+    m2: T,
+    r: [T; 2],
+) -> Option<([LorentzVector<T>; 2], T)> {
+    if p.t <= p.spatial_distance() {
+        return None;
+    }
+    let m = p.m();
+    let (momenta, q) = two_body_decay(p, m, m1, m2, r)?;
+    let four_pi = T::from(4. * std::f64::consts::PI).unwrap();
+    Some((momenta, q / (four_pi * m)))
+}
+
+/// Map the point `r` in `[0, 1)^5` to the three-body phase space of a system with momentum
+/// `p` decaying into particles with masses `masses`, for `1 -> 3` decays, or `2 -> 3`
+/// scattering with `p = p_a + p_b`.
+///
+/// The invariant mass squared `s23` of particles 2 and 3 is uniform in its kinematic range
+/// and given by `r[0]`. The direction of particle 1 in the rest frame of `p` is given by
+/// `r[1]` and `r[2]`, and the direction of particle 2 in the rest frame of the (23) system
+/// by `r[3]` and `r[4]`. The returned weight is the analytic Jacobian
+/// `q1 q23 (s23_max - s23_min) / (32 pi^3 m sqrt(s23))`, with the normalization of
+/// `two_body_phase_space()`. Returns `None` if `p` is below the threshold.
+pub fn three_body_phase_space<T: Float + Field>(
+    p: &LorentzVector<T>,
+    masses: [T; 3],
+    r: [T; 5],
+) -> Option<([LorentzVector<T>; 3], T)> {
+    if p.t <= p.spatial_distance() {
+        return None;
+    }
+    let m = p.m();
+    let [m1, m2, m3] = masses;
+    let s_min = (m2 + m3) * (m2 + m3);
+    let s_max = (m - m1) * (m - m1);
+    if m <= m1 + m2 + m3 {
+        return None;
+    }
+
+    let s23 = s_min + (s_max - s_min) * r[0];
+    // guard against rounding below the threshold of the (23) system
+    let m23 = s23.sqrt().max(m2 + m3);
+    let ([p1, p23], q1) = two_body_decay(p, m, m1, m23, [r[1], r[2]])?;
+    let ([p2, p3], q23) = two_body_decay(&p23, m23, m2, m3, [r[3], r[4]])?;
+
+    let pi = T::from(std::f64::consts::PI).unwrap();
+    let weight = q1 * q23 * (s_max - s_min) / (T::from(32.).unwrap() * pi * pi * pi * m * m23);
+    Some(([p1, p2, p3], weight))
+}
+
+/// Emit a photon collinear to `beam` carrying the fraction `x` of its plus component along
+/// the beam axis, and return the photon and the reduced beam. The minus component of the
+/// reduced beam is rescaled by `1 / (1 - x)` to keep it on its mass shell, so that
+/// momentum is conserved up to terms of order `x m^2 / E`, and exactly for massless beams.
+/// Returns `None` if `x` is outside of `[0, 1)` or if `beam` has no direction.
+pub fn isr_emission<T: Float + Field>(
+    beam: &LorentzVector<T>,
+    x: T,
+) -> Option<(LorentzVector<T>, LorentzVector<T>)> {
+    let p_abs = beam.spatial_distance();
+    if !(x >= T::zero() && x < T::one()) || p_abs == T::zero() {
+        return None;
+    }
+
+    let two = T::one() + T::one();
+    let (nx, ny, nz) = (beam.x / p_abs, beam.y / p_abs, beam.z / p_abs);
+    let na = LorentzVector::from_args(T::one(), nx, ny, nz);
+    let nb = LorentzVector::from_args(T::one(), -nx, -ny, -nz);
+    let plus = beam.t + p_abs;
+    // computed from the mass to avoid the cancellation in `E - |p|`
+    let minus = beam.square() / plus;
+
+    let photon = na * (x * plus / two);
+    let reduced = na * ((T::one() - x) * plus / two) + nb * (minus / (two * (T::one() - x)));
+    Some((photon, reduced))
+}
+
+/// An initial-state photon emission generated by `isr_mapping()`.
+#[derive(Debug, Copy, Clone)]
+pub struct IsrEmission<T: Field> {
+    pub photon: LorentzVector<T>,
+    pub beam: LorentzVector<T>,
+    pub x: T,
+    pub jacobian: T,
+}
+
+/// Map `r` in `[0, 1)` to the fraction `x` of an initial-state photon emission off `beam`
+/// in `[0, x_max]`, sampled from the density `beta x^(beta - 1) / x_max^beta` of the
+/// soft-photon limit of the electron structure function, and emit the photon with
+/// `isr_emission()`. The returned Jacobian is the inverse of this density, such that the
+/// average of the Jacobian times a function of `x` estimates its integral over `x`.
+/// The partonic system is formed by the reduced beam and the other incoming momentum.
+pub fn isr_mapping<T: Float + Field>(
+    beam: &LorentzVector<T>,
+    r: T,
+    beta: T,
+    x_max: T,
+) -> Option<IsrEmission<T>> {
+    if beta <= T::zero() || x_max <= T::zero() {
+        return None;
+    }
+
+    let x = x_max * r.powf(T::one() / beta);
+    let (photon, reduced) = isr_emission(beam, x)?;
+    let jacobian = x_max.powf(beta) * x.powf(T::one() - beta) / beta;
+    Some(IsrEmission {
+        photon,
+        beam: reduced,
+        x,
+        jacobian,
+    })
+}
@@ -0,0 +1,225 @@
+use crate::{Field, LorentzVector};
+
+const INVALID_LENGTH: &str = "Invalid list length for LorentzVector conversion";
+
+/// Create a vector from the components extracted from a Python sequence.
+/// Sequences of three components are spatial vectors with a vanishing time component.
+/// This is shared by the conversions of all Python bindings and element types.
+#[inline]
+fn from_components<T: Field>(v: &[T]) -> Option<LorentzVector<T>> {
+    match v.len() {
+        3 => Some(LorentzVector::from_args(T::zero(), v[0], v[1], v[2])),
+        4 => Some(LorentzVector::from_args(v[0], v[1], v[2], v[3])),
+        _ => None,
+    }
+}
+
+/// A sample of events with a varying number of momenta, which can be extracted from a
+/// Python sequence of sequences of four-component sequences in one call.
+#[derive(Debug, Clone, Default)]
+pub struct Events(pub Vec<Vec<LorentzVector<f64>>>);
+
+#[cfg(feature = "pyo3_support")]
+mod pyo3_conversion {
+    use super::{from_components, Events, INVALID_LENGTH};
+    use crate::{Field, LorentzVector};
+    use num::Complex;
+    use pyo3::types::{PyAny, PyFloat, PyList, PySequence, PyTuple};
+    use pyo3::{FromPyObject, IntoPy, PyObject, PyResult, Python, ToPyObject};
+
+    /// Convert a vector to a list, with `convert` converting a single component.
+    fn to_list<T: Field, F: Fn(T) -> PyObject>(
+        py: Python,
+        v: &LorentzVector<T>,
+        convert: F,
+    ) -> PyObject {
+        PyList::new(py, [convert(v.t), convert(v.x), convert(v.y), convert(v.z)]).to_object(py)
+    }
+
+    /// Extract a vector from a sequence, with `extract` converting a single component.
+    fn extract_vector<'s, T: Field, F: Fn(&'s PyAny) -> PyResult<T>>(
+        obj: &'s PyAny,
+        extract: F,
+    ) -> PyResult<LorentzVector<T>> {
+        let seq = obj.cast_as::<PySequence>()?;
+        let mut v = Vec::new();
+        for item in seq.iter()? {
+            v.push(extract(item?)?);
+        }
+
+        from_components(&v).ok_or_else(|| pyo3::exceptions::PyTypeError::new_err(INVALID_LENGTH))
+    }
+
+    fn complex_to_object(py: Python, c: Complex<f64>) -> PyObject {
+        PyTuple::new(py, [PyFloat::new(py, c.re), PyFloat::new(py, c.im)]).to_object(py)
+    }
+
+    fn extract_complex(obj: &PyAny) -> PyResult<Complex<f64>> {
+        let seq = obj.cast_as::<PySequence>()?;
+        Ok(Complex::new(
+            f64::extract(seq.get_item(0)?)?,
+            f64::extract(seq.get_item(1)?)?,
+        ))
+    }
+
+    impl IntoPy<PyObject> for LorentzVector<f64> {
+        #[inline]
+        fn into_py(self, py: Python) -> PyObject {
+            self.to_object(py)
+        }
+    }
+
+    impl ToPyObject for LorentzVector<f64> {
+        fn to_object(&self, py: Python) -> PyObject {
+            to_list(py, self, |c| PyFloat::new(py, c).to_object(py))
+        }
+    }
+
+    impl IntoPy<PyObject> for LorentzVector<Complex<f64>> {
+        #[inline]
+        fn into_py(self, py: Python) -> PyObject {
+            self.to_object(py)
+        }
+    }
+
+    impl ToPyObject for LorentzVector<Complex<f64>> {
+        fn to_object(&self, py: Python) -> PyObject {
+            to_list(py, self, |c| complex_to_object(py, c))
+        }
+    }
+
+    impl<'s> FromPyObject<'s> for LorentzVector<Complex<f64>> {
+        fn extract(obj: &'s PyAny) -> PyResult<Self> {
+            extract_vector(obj, extract_complex)
+        }
+    }
+
+    impl<'s> FromPyObject<'s> for LorentzVector<f64> {
+        fn extract(obj: &'s PyAny) -> PyResult<Self> {
+            extract_vector(obj, f64::extract)
+        }
+    }
+
+    impl<'s> FromPyObject<'s> for Events {
+        fn extract(obj: &'s PyAny) -> PyResult<Self> {
+            let seq = obj.cast_as::<PySequence>()?;
+            let mut events = Vec::with_capacity(seq.len()? as usize);
+            for event in seq.iter()? {
+                let event = event?.cast_as::<PySequence>()?;
+                let mut momenta = Vec::with_capacity(event.len()? as usize);
+                for p in event.iter()? {
+                    momenta.push(extract_vector(p?, f64::extract)?);
+                }
+                events.push(momenta);
+            }
+            Ok(Events(events))
+        }
+    }
+}
+
+#[cfg(feature = "cpython_support")]
+mod cpython_conversion {
+    use super::{from_components, Events, INVALID_LENGTH};
+    use crate::{Field, LorentzVector};
+    use cpython::{
+        exc, FromPyObject, PyDrop, PyErr, PyFloat, PyList, PyObject, PyResult, PySequence, PyTuple,
+        Python, PythonObject, ToPyObject,
+    };
+    use num::Complex;
+
+    /// Convert a vector to a list, with `convert` converting a single component.
+    fn to_list<T: Field, F: Fn(T) -> PyObject>(
+        py: Python,
+        v: &LorentzVector<T>,
+        convert: F,
+    ) -> PyList {
+        PyList::new(
+            py,
+            &[convert(v.t), convert(v.x), convert(v.y), convert(v.z)],
+        )
+    }
+
+    /// Extract a vector from a sequence, with `extract` converting a single component.
+    fn extract_vector<T: Field, F: Fn(Python, &PyObject) -> PyResult<T>>(
+        py: Python,
+        obj: &PyObject,
+        extract: F,
+    ) -> PyResult<LorentzVector<T>> {
+        let seq = obj.cast_as::<PySequence>(py)?;
+        let mut v = Vec::new();
+        for item in seq.iter(py)? {
+            let item = item?;
+            v.push(extract(py, &item)?);
+            item.release_ref(py);
+        }
+
+        from_components(&v).ok_or_else(|| PyErr::new::<exc::TypeError, _>(py, INVALID_LENGTH))
+    }
+
+    fn complex_to_object(py: Python, c: Complex<f64>) -> PyObject {
+        PyTuple::new(
+            py,
+            &[
+                PyFloat::new(py, c.re).into_object(),
+                PyFloat::new(py, c.im).into_object(),
+            ],
+        )
+        .into_object()
+    }
+
+    fn extract_complex(py: Python, obj: &PyObject) -> PyResult<Complex<f64>> {
+        let seq = obj.cast_as::<PySequence>(py)?;
+        Ok(Complex::new(
+            f64::extract(py, &seq.get_item(py, 0)?)?,
+            f64::extract(py, &seq.get_item(py, 1)?)?,
+        ))
+    }
+
+    impl ToPyObject for LorentzVector<f64> {
+        type ObjectType = PyList;
+
+        fn to_py_object(&self, py: Python) -> PyList {
+            to_list(py, self, |c| PyFloat::new(py, c).into_object())
+        }
+    }
+
+    impl ToPyObject for LorentzVector<Complex<f64>> {
+        type ObjectType = PyList;
+
+        fn to_py_object(&self, py: Python) -> PyList {
+            to_list(py, self, |c| complex_to_object(py, c))
+        }
+    }
+
+    impl<'s> FromPyObject<'s> for LorentzVector<Complex<f64>> {
+        fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+            extract_vector(py, obj, extract_complex)
+        }
+    }
+
+    impl<'s> FromPyObject<'s> for LorentzVector<f64> {
+        fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+            extract_vector(py, obj, |py, c| f64::extract(py, c))
+        }
+    }
+
+    impl<'s> FromPyObject<'s> for Events {
+        fn extract(py: Python, obj: &'s PyObject) -> PyResult<Self> {
+            let seq = obj.cast_as::<PySequence>(py)?;
+            let mut events = Vec::with_capacity(seq.len(py)? as usize);
+            for event in seq.iter(py)? {
+                let event = event?;
+                let event_seq = event.cast_as::<PySequence>(py)?;
+                let mut momenta = Vec::with_capacity(event_seq.len(py)? as usize);
+                for p in event_seq.iter(py)? {
+                    let p = p?;
+                    momenta.push(extract_vector(py, &p, |py, c| f64::extract(py, c))?);
+                    p.release_ref(py);
+                }
+                events.push(momenta);
+                event.release_ref(py);
+            }
+            Ok(Events(events))
+        }
+    }
+}
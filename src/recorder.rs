@@ -0,0 +1,121 @@
+use crate::{Field, LorentzVector};
+use num::Float;
+use std::fmt;
+use std::fmt::Display;
+
+/// A transformation applied to a `TrackedVector`.
+#[derive(Debug, Clone)]
+pub struct TransformationRecord<T: Field> {
+    /// The name of the transformation.
+    pub kind: &'static str,
+    /// The parameters of the transformation, such as the components of a boost vector.
+    pub parameters: Vec<T>,
+    pub before: LorentzVector<T>,
+    pub after: LorentzVector<T>,
+}
+
+/// A vector that records every transformation applied to it, with the vector and its
+/// invariant before and after, for debugging long chains of frame transformations.
+/// The recorded chain is printed as a report with `Display`.
+#[derive(Debug, Clone)]
+pub struct TrackedVector<T: Float + Field> {
+    value: LorentzVector<T>,
+    records: Vec<TransformationRecord<T>>,
+}
+
+impl<T: Float + Field> TrackedVector<T> {
+    pub fn new(value: LorentzVector<T>) -> TrackedVector<T> {
+        TrackedVector {
+            value,
+            records: vec![],
+        }
+    }
+
+    /// The current value of the vector.
+    #[inline]
+    pub fn value(&self) -> &LorentzVector<T> {
+        &self.value
+    }
+
+    #[inline]
+    pub fn records(&self) -> &[TransformationRecord<T>] {
+        &self.records
+    }
+
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Apply and record an arbitrary transformation.
+    pub fn apply<F: FnOnce(&LorentzVector<T>) -> LorentzVector<T>>(
+        &mut self,
+        kind: &'static str,
+        parameters: &[T],
+        transformation: F,
+    ) -> &mut TrackedVector<T> {
+        let after = transformation(&self.value);
+        self.records.push(TransformationRecord {
+            kind,
+            parameters: parameters.to_vec(),
+            before: self.value,
+            after,
+        });
+        self.value = after;
+        self
+    }
+
+    pub fn boost(&mut self, boost_vector: &LorentzVector<T>) -> &mut TrackedVector<T> {
+        self.apply(
+            "boost",
+            &[boost_vector.x, boost_vector.y, boost_vector.z],
+            |v| v.boost(boost_vector),
+        )
+    }
+
+    pub fn boost_from_to(
+        &mut self,
+        p: &LorentzVector<T>,
+        q: &LorentzVector<T>,
+    ) -> &mut TrackedVector<T> {
+        self.apply(
+            "boost_from_to",
+            &[p.t, p.x, p.y, p.z, q.t, q.x, q.y, q.z],
+            |v| v.boost_from_to(p, q),
+        )
+    }
+
+    pub fn parity(&mut self) -> &mut TrackedVector<T> {
+        self.apply("parity", &[], |v| v.parity())
+    }
+
+    pub fn reflect(&mut self, normal: &LorentzVector<T>) -> &mut TrackedVector<T> {
+        self.apply("reflect", &[normal.x, normal.y, normal.z], |v| {
+            v.reflect(normal)
+        })
+    }
+
+    pub fn with_mass(&mut self, m: T) -> &mut TrackedVector<T> {
+        self.apply("with_mass", &[m], |v| v.with_mass(m))
+    }
+}
+
+impl<T: Float + Field> Display for TrackedVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, r) in self.records.iter().enumerate() {
+            write!(f, "{} {}(", i, r.kind)?;
+            for (j, p) in r.parameters.iter().enumerate() {
+                write!(f, "{}{}", if j == 0 { "" } else { ", " }, p)?;
+            }
+            writeln!(
+                f,
+                "): {} -> {}, p^2: {} -> {} (change {})",
+                r.before,
+                r.after,
+                r.before.square(),
+                r.after.square(),
+                r.after.square() - r.before.square()
+            )?;
+        }
+        write!(f, "current: {}", self.value)
+    }
+}
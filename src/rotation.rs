@@ -0,0 +1,155 @@
+use crate::{Field, LorentzTransformation, LorentzVector};
+use num::Float;
+use std::ops::Mul;
+
+/// A rotation of the spatial part of Lorentz vectors, stored as a unit quaternion
+/// `w + x i + y j + z k`. Rotations are composed with `*`, where `(a * b)` applies `b`
+/// first. Composing quaternions accumulates less rounding error than multiplying 3x3
+/// matrices, and the drift that remains can be removed with `normalize()`.
+#[derive(Debug, Copy, Clone)]
+pub struct Rotation<T: Field> {
+    w: T,
+    x: T,
+    y: T,
+    z: T,
+}
+
+impl<T: Field> Default for Rotation<T> {
+    fn default() -> Rotation<T> {
+        Rotation::identity()
+    }
+}
+
+impl<T: Field> Rotation<T> {
+    #[inline]
+    pub fn identity() -> Rotation<T> {
+        Rotation {
+            w: T::one(),
+            x: T::zero(),
+            y: T::zero(),
+            z: T::zero(),
+        }
+    }
+
+    /// The components `(w, x, y, z)` of the quaternion.
+    #[inline]
+    pub fn quaternion(&self) -> (T, T, T, T) {
+        (self.w, self.x, self.y, self.z)
+    }
+
+    /// The inverse rotation, which is the conjugate of the unit quaternion.
+    #[inline]
+    pub fn inverse(&self) -> Rotation<T> {
+        Rotation {
+            w: self.w,
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+
+    /// Rotate the spatial part of `v`, keeping the time component.
+    #[inline]
+    pub fn apply(&self, v: &LorentzVector<T>) -> LorentzVector<T> {
+        // v' = v + 2 w (q x v) + 2 q x (q x v)
+        let two = T::one() + T::one();
+        let cx = (self.y * v.z - self.z * v.y) * two;
+        let cy = (self.z * v.x - self.x * v.z) * two;
+        let cz = (self.x * v.y - self.y * v.x) * two;
+        LorentzVector::from_args(
+            v.t,
+            v.x + self.w * cx + self.y * cz - self.z * cy,
+            v.y + self.w * cy + self.z * cx - self.x * cz,
+            v.z + self.w * cz + self.x * cy - self.y * cx,
+        )
+    }
+
+    /// Create the 4x4 matrix of the rotation.
+    pub fn to_transformation(&self) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| self.apply(e))
+    }
+}
+
+impl<T: Float + Field> Rotation<T> {
+    /// Create the rotation from the quaternion `w + x i + y j + z k`, which is normalized.
+    pub fn from_quaternion(w: T, x: T, y: T, z: T) -> Rotation<T> {
+        Rotation { w, x, y, z }.normalize()
+    }
+
+    /// Create the counter-clockwise rotation by `angle` around the spatial part of `axis`,
+    /// with the convention of `LorentzVector::rotate_axis_angle()`. The axis does not have
+    /// to be normalized. A vanishing axis has no direction, and yields the identity.
+    pub fn from_axis_angle(axis: &LorentzVector<T>, angle: T) -> Rotation<T> {
+        let norm = axis.spatial_distance();
+        if norm == T::zero() {
+            return Rotation::identity();
+        }
+        let two = T::one() + T::one();
+        let (sin, cos) = (angle / two).sin_cos();
+        let s = sin / norm;
+        Rotation {
+            w: cos,
+            x: axis.x * s,
+            y: axis.y * s,
+            z: axis.z * s,
+        }
+    }
+
+    /// The normalized axis, as the spatial part of a vector, and the angle in `[0, 2pi]`
+    /// of the rotation. For the identity, the axis is the z-axis.
+    pub fn axis_angle(&self) -> (LorentzVector<T>, T) {
+        let v = LorentzVector::from_args(T::zero(), self.x, self.y, self.z);
+        let sin = v.spatial_distance();
+        let two = T::one() + T::one();
+        if sin == T::zero() {
+            return (
+                LorentzVector::from_args(T::zero(), T::zero(), T::zero(), T::one()),
+                T::zero(),
+            );
+        }
+        (v / sin, two * sin.atan2(self.w))
+    }
+
+    /// Rescale the quaternion to unit norm, to remove the drift of a long chain of
+    /// compositions.
+    pub fn normalize(&self) -> Rotation<T> {
+        let norm = (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        Rotation {
+            w: self.w / norm,
+            x: self.x / norm,
+            y: self.y / norm,
+            z: self.z / norm,
+        }
+    }
+}
+
+impl<T: Field> Mul<Rotation<T>> for Rotation<T> {
+    type Output = Rotation<T>;
+
+    fn mul(self, other: Rotation<T>) -> Rotation<T> {
+        Rotation {
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+        }
+    }
+}
+
+impl<T: Field> Mul<&LorentzVector<T>> for &Rotation<T> {
+    type Output = LorentzVector<T>;
+
+    #[inline]
+    fn mul(self, other: &LorentzVector<T>) -> LorentzVector<T> {
+        self.apply(other)
+    }
+}
+
+impl<T: Field> Mul<LorentzVector<T>> for Rotation<T> {
+    type Output = LorentzVector<T>;
+
+    #[inline]
+    fn mul(self, other: LorentzVector<T>) -> LorentzVector<T> {
+        self.apply(&other)
+    }
+}
@@ -0,0 +1,22 @@
+#[cfg(feature = "serde_support")]
+use crate::{Field, LorentzVector};
+#[cfg(feature = "serde_support")]
+use serde::ser::{Serialize, SerializeTuple, Serializer};
+
+/// Vectors are serialized as a tuple of four components, which is the format read by the
+/// `Deserialize` implementation. Serializers that write floats in their shortest
+/// round-trip representation, such as `serde_json`, preserve the exact bits.
+#[cfg(feature = "serde_support")]
+impl<T: Field + Serialize> Serialize for LorentzVector<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut tuple = serializer.serialize_tuple(4)?;
+        tuple.serialize_element(&self.t)?;
+        tuple.serialize_element(&self.x)?;
+        tuple.serialize_element(&self.y)?;
+        tuple.serialize_element(&self.z)?;
+        tuple.end()
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{LorentzTransformation, LorentzVector, RealNumberLike, Rotation};
+use num::Complex;
+use std::ops::Mul;
+
+type Matrix2<T> = [[Complex<T>; 2]; 2];
+
+#[inline]
+fn mul_2x2<T: RealNumberLike>(a: &Matrix2<T>, b: &Matrix2<T>) -> Matrix2<T> {
+    [
+        [
+            a[0][0] * b[0][0] + a[0][1] * b[1][0],
+            a[0][0] * b[0][1] + a[0][1] * b[1][1],
+        ],
+        [
+            a[1][0] * b[0][0] + a[1][1] * b[1][0],
+            a[1][0] * b[0][1] + a[1][1] * b[1][1],
+        ],
+    ]
+}
+
+/// A proper orthochronous Lorentz transformation in its SL(2,C) representation: a complex
+/// 2x2 matrix `A` with unit determinant that acts on a vector `p` as
+/// `p.sigma -> A (p.sigma) A^dagger`, where `p.sigma = t + x sigma_x + y sigma_y + z sigma_z`.
+/// Composition with `*` is a product of 2x2 matrices, where `(a * b)` applies `b` first.
+/// `A` and `-A` represent the same transformation of vectors, but differ for spinors.
+#[derive(Debug, Copy, Clone)]
+pub struct SpinorTransformation<T: RealNumberLike> {
+    matrix: Matrix2<T>,
+}
+
+impl<T: RealNumberLike> Default for SpinorTransformation<T> {
+    fn default() -> SpinorTransformation<T> {
+        SpinorTransformation::identity()
+    }
+}
+
+impl<T: RealNumberLike> SpinorTransformation<T> {
+    #[inline]
+    pub fn identity() -> SpinorTransformation<T> {
+        let (zero, one) = (
+            Complex::new(T::zero(), T::zero()),
+            Complex::new(T::one(), T::zero()),
+        );
+        SpinorTransformation {
+            matrix: [[one, zero], [zero, one]],
+        }
+    }
+
+    /// Create a transformation from a row-major matrix, which should have unit determinant.
+    #[inline]
+    pub fn from_matrix(matrix: [[Complex<T>; 2]; 2]) -> SpinorTransformation<T> {
+        SpinorTransformation { matrix }
+    }
+
+    /// The row-major matrix.
+    #[inline]
+    pub fn matrix(&self) -> &[[Complex<T>; 2]; 2] {
+        &self.matrix
+    }
+
+    #[inline]
+    pub fn determinant(&self) -> Complex<T> {
+        let m = &self.matrix;
+        m[0][0] * m[1][1] - m[0][1] * m[1][0]
+    }
+
+    /// The conjugate transpose of the matrix.
+    #[inline]
+    pub fn adjoint(&self) -> SpinorTransformation<T> {
+        let m = &self.matrix;
+        SpinorTransformation {
+            matrix: [
+                [m[0][0].conj(), m[1][0].conj()],
+                [m[0][1].conj(), m[1][1].conj()],
+            ],
+        }
+    }
+
+    /// The inverse, assuming that the determinant is one.
+    #[inline]
+    pub fn inverse(&self) -> SpinorTransformation<T> {
+        let m = &self.matrix;
+        SpinorTransformation {
+            matrix: [[m[1][1], -m[0][1]], [-m[1][0], m[0][0]]],
+        }
+    }
+
+    #[inline]
+    pub fn apply(&self, v: &LorentzVector<T>) -> LorentzVector<T> {
+        let p = [
+            [Complex::new(v.t + v.z, T::zero()), Complex::new(v.x, -v.y)],
+            [Complex::new(v.x, v.y), Complex::new(v.t - v.z, T::zero())],
+        ];
+        let r = mul_2x2(&mul_2x2(&self.matrix, &p), &self.adjoint().matrix);
+        let two = T::one() + T::one();
+        LorentzVector::from_args(
+            (r[0][0].re + r[1][1].re) / two,
+            r[1][0].re,
+            r[1][0].im,
+            (r[0][0].re - r[1][1].re) / two,
+        )
+    }
+
+    /// Create the 4x4 matrix of the transformation.
+    pub fn to_transformation(&self) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| self.apply(e))
+    }
+
+    /// Create the SL(2,C) matrix of a proper orthochronous Lorentz transformation, with the
+    /// sign chosen such that the trace has a non-negative real part. The result is
+    /// meaningless for other transformations; use `is_lorentz()` and
+    /// `is_proper_orthochronous()` to validate the input.
+    pub fn from_transformation(
+        transformation: &LorentzTransformation<T>,
+    ) -> SpinorTransformation<T> {
+        // sum_{mu,nu} L^mu_nu sigma_mu C sigma_nu = 2 tr(A^dagger C) A for any C, so that
+        // one of the Pauli matrices for C gives a result that is not degenerate
+        let (zero, one) = (
+            Complex::new(T::zero(), T::zero()),
+            Complex::new(T::one(), T::zero()),
+        );
+        let im = Complex::i();
+        let sigma = [
+            [[one, zero], [zero, one]],
+            [[zero, one], [one, zero]],
+            [[zero, -im], [im, zero]],
+            [[one, zero], [zero, -one]],
+        ];
+
+        let m = transformation.matrix();
+        let mut best = ([[zero; 2]; 2], -T::one());
+        for c in &sigma {
+            let mut a = [[zero; 2]; 2];
+            for (mu, s_mu) in sigma.iter().enumerate() {
+                let s_mu_c = mul_2x2(s_mu, c);
+                for (nu, s_nu) in sigma.iter().enumerate() {
+                    let term = mul_2x2(&s_mu_c, s_nu);
+                    for (row, term_row) in a.iter_mut().zip(term.iter()) {
+                        for (e, t) in row.iter_mut().zip(term_row.iter()) {
+                            *e += *t * m[mu][nu];
+                        }
+                    }
+                }
+            }
+
+            let norm: T = a.iter().flat_map(|r| r.iter()).map(|e| e.norm_sqr()).sum();
+            if norm > best.1 {
+                best = (a, norm);
+            }
+        }
+
+        let mut a = SpinorTransformation { matrix: best.0 };
+        let scale = a.determinant().sqrt();
+        for e in a.matrix.iter_mut().flat_map(|r| r.iter_mut()) {
+            *e = *e / scale;
+        }
+        if (a.matrix[0][0] + a.matrix[1][1]).re < T::zero() {
+            for e in a.matrix.iter_mut().flat_map(|r| r.iter_mut()) {
+                *e = -*e;
+            }
+        }
+        a
+    }
+
+    /// Create the SL(2,C) matrix `w - i (x sigma_x + y sigma_y + z sigma_z)` of the
+    /// rotation with quaternion `w + x i + y j + z k`.
+    pub fn from_rotation(rotation: &Rotation<T>) -> SpinorTransformation<T> {
+        let (w, x, y, z) = rotation.quaternion();
+        SpinorTransformation {
+            matrix: [
+                [Complex::new(w, -z), Complex::new(-y, -x)],
+                [Complex::new(y, -x), Complex::new(w, z)],
+            ],
+        }
+    }
+}
+
+impl<T: RealNumberLike> Mul<SpinorTransformation<T>> for SpinorTransformation<T> {
+    type Output = SpinorTransformation<T>;
+
+    fn mul(self, other: SpinorTransformation<T>) -> SpinorTransformation<T> {
+        SpinorTransformation {
+            matrix: mul_2x2(&self.matrix, &other.matrix),
+        }
+    }
+}
+
+impl<T: RealNumberLike> Mul<&LorentzVector<T>> for &SpinorTransformation<T> {
+    type Output = LorentzVector<T>;
+
+    #[inline]
+    fn mul(self, other: &LorentzVector<T>) -> LorentzVector<T> {
+        self.apply(other)
+    }
+}
+
+impl<T: RealNumberLike> Mul<LorentzVector<T>> for SpinorTransformation<T> {
+    type Output = LorentzVector<T>;
+
+    #[inline]
+    fn mul(self, other: LorentzVector<T>) -> LorentzVector<T> {
+        self.apply(&other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(m: [[Complex<f64>; 2]; 2]) -> SpinorTransformation<f64> {
+        let scale = SpinorTransformation::from_matrix(m).determinant().sqrt();
+        SpinorTransformation::from_matrix([
+            [m[0][0] / scale, m[0][1] / scale],
+            [m[1][0] / scale, m[1][1] / scale],
+        ])
+    }
+
+    #[test]
+    fn from_transformation_round_trip() {
+        let c = Complex::new;
+        let matrices = [
+            [[c(0.3, -1.2), c(0.7, 0.4)], [c(-0.5, 0.9), c(1.1, 0.2)]],
+            [[c(-1.4, 0.3), c(0.2, -0.6)], [c(0.8, 0.1), c(-0.3, -0.9)]],
+            [[c(-2.0, 0.5), c(0.1, 0.0)], [c(0.3, -0.2), c(-0.4, 0.0)]],
+            [[c(0.2, 1.5), c(-0.9, 0.3)], [c(0.4, 0.7), c(0.1, -1.3)]],
+            // a rotation by pi around the z-axis, which has a vanishing trace
+            [[c(0.0, -1.0), c(0.0, 0.0)], [c(0.0, 0.0), c(0.0, 1.0)]],
+        ];
+
+        for m in matrices.iter() {
+            let a = normalized(*m);
+            let b = SpinorTransformation::from_transformation(&a.to_transformation());
+
+            let distance = |sign: f64| {
+                a.matrix()
+                    .iter()
+                    .flatten()
+                    .zip(b.matrix().iter().flatten())
+                    .map(|(x, y)| (*x * sign - *y).norm())
+                    .fold(0.0, f64::max)
+            };
+            assert!(distance(1.0).min(distance(-1.0)) < 1e-10);
+
+            let trace_a = a.matrix()[0][0] + a.matrix()[1][1];
+            let trace_b = b.matrix()[0][0] + b.matrix()[1][1];
+            assert!(trace_b.re >= -1e-12);
+            if trace_a.re.abs() > 1e-6 {
+                assert!(distance(trace_a.re.signum()) < 1e-10);
+            }
+        }
+    }
+}
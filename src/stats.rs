@@ -0,0 +1,199 @@
+use num::Float;
+
+/// An accumulator for the weighted mean and variance of an observable.
+/// Negative weights are supported. The sums are kept relative to the first value
+/// that is added, which avoids the catastrophic cancellation of naive sums of squares.
+#[derive(Debug, Copy, Clone)]
+pub struct WeightedStats<T: Float> {
+    count: usize,
+    shift: T,
+    sum_w: T,
+    sum_w2: T,
+    sum_wd: T,
+    sum_wd2: T,
+}
+
+impl<T: Float> Default for WeightedStats<T> {
+    fn default() -> WeightedStats<T> {
+        WeightedStats::new()
+    }
+}
+
+impl<T: Float> WeightedStats<T> {
+    pub fn new() -> WeightedStats<T> {
+        WeightedStats {
+            count: 0,
+            shift: T::zero(),
+            sum_w: T::zero(),
+            sum_w2: T::zero(),
+            sum_wd: T::zero(),
+            sum_wd2: T::zero(),
+        }
+    }
+
+    /// Add the value `x` of an observable with event weight `w`.
+    #[inline]
+    pub fn add(&mut self, x: T, w: T) {
+        if self.count == 0 {
+            self.shift = x;
+        }
+        let d = x - self.shift;
+        self.count += 1;
+        self.sum_w = self.sum_w + w;
+        self.sum_w2 = self.sum_w2 + w * w;
+        self.sum_wd = self.sum_wd + w * d;
+        self.sum_wd2 = self.sum_wd2 + w * d * d;
+    }
+
+    /// Combine with the statistics of another accumulator.
+    pub fn merge(&mut self, other: &WeightedStats<T>) {
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *other;
+            return;
+        }
+
+        // express the sums of other relative to our shift
+        let k = other.shift - self.shift;
+        self.count += other.count;
+        self.sum_w = self.sum_w + other.sum_w;
+        self.sum_w2 = self.sum_w2 + other.sum_w2;
+        self.sum_wd2 = self.sum_wd2 + other.sum_wd2 + (k + k) * other.sum_wd + k * k * other.sum_w;
+        self.sum_wd = self.sum_wd + other.sum_wd + k * other.sum_w;
+    }
+
+    /// The number of values added.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    #[inline]
+    pub fn sum_of_weights(&self) -> T {
+        self.sum_w
+    }
+
+    /// The weighted mean. This is NaN if the weights sum to zero.
+    #[inline]
+    pub fn mean(&self) -> T {
+        self.shift + self.sum_wd / self.sum_w
+    }
+
+    /// The weighted variance of the distribution of the observable.
+    #[inline]
+    pub fn variance(&self) -> T {
+        (self.sum_wd2 - self.sum_wd * self.sum_wd / self.sum_w) / self.sum_w
+    }
+
+    /// The effective number of events `(sum w)^2 / sum w^2`.
+    #[inline]
+    pub fn effective_n(&self) -> T {
+        if self.sum_w2 == T::zero() {
+            T::zero()
+        } else {
+            self.sum_w * self.sum_w / self.sum_w2
+        }
+    }
+
+    /// The estimated error on the weighted mean.
+    #[inline]
+    pub fn error_of_mean(&self) -> T {
+        (self.variance() / self.effective_n()).sqrt()
+    }
+}
+
+/// Hit-or-miss unweighting of a stream of weighted events.
+/// Events with a weight above the maximum weight are kept with their original
+/// weight and are reported as over-weight.
+#[derive(Debug, Copy, Clone)]
+pub struct Unweighter<T: Float> {
+    max_weight: T,
+    largest_weight: T,
+    processed: usize,
+    accepted: usize,
+    over_weight: usize,
+}
+
+impl<T: Float> Unweighter<T> {
+    pub fn new(max_weight: T) -> Unweighter<T> {
+        Unweighter {
+            max_weight,
+            largest_weight: T::zero(),
+            processed: 0,
+            accepted: 0,
+            over_weight: 0,
+        }
+    }
+
+    /// Decide whether to keep an event with weight `weight`, using a uniform random
+    /// number `r` in `[0, 1)`. Returns the new weight of an accepted event, which is
+    /// `±max_weight` unless the event is over-weight.
+    pub fn unweight(&mut self, weight: T, r: T) -> Option<T> {
+        let abs_weight = weight.abs();
+        self.processed += 1;
+        if abs_weight > self.largest_weight {
+            self.largest_weight = abs_weight;
+        }
+
+        if abs_weight > self.max_weight {
+            self.over_weight += 1;
+            self.accepted += 1;
+            return Some(weight);
+        }
+
+        if r * self.max_weight < abs_weight {
+            self.accepted += 1;
+            Some(if weight < T::zero() {
+                -self.max_weight
+            } else {
+                self.max_weight
+            })
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    pub fn max_weight(&self) -> T {
+        self.max_weight
+    }
+
+    #[inline]
+    pub fn set_max_weight(&mut self, max_weight: T) {
+        self.max_weight = max_weight;
+    }
+
+    /// The largest absolute weight encountered so far.
+    #[inline]
+    pub fn largest_weight(&self) -> T {
+        self.largest_weight
+    }
+
+    #[inline]
+    pub fn processed(&self) -> usize {
+        self.processed
+    }
+
+    #[inline]
+    pub fn accepted(&self) -> usize {
+        self.accepted
+    }
+
+    /// The number of events with a weight above the maximum weight.
+    #[inline]
+    pub fn over_weight(&self) -> usize {
+        self.over_weight
+    }
+
+    /// The fraction of processed events that was accepted.
+    #[inline]
+    pub fn efficiency(&self) -> T {
+        if self.processed == 0 {
+            T::zero()
+        } else {
+            T::from(self.accepted).unwrap() / T::from(self.processed).unwrap()
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Field, LorentzVector};
+use num::Float;
+
+/// Compute `s = (m1 + m2)^2 / (1 - beta^2)`, the squared center of mass energy at which the
+/// pair `m1 m2` is produced with velocity parameter `beta`, where
+/// `beta^2 = 1 - (m1 + m2)^2 / s`. For equal masses `beta` is the velocity of either
+/// particle in the center of mass frame.
+#[inline]
+pub fn threshold_s<T: Float>(m1: T, m2: T, beta: T) -> T {
+    (m1 + m2) * (m1 + m2) / ((T::one() - beta) * (T::one() + beta))
+}
+
+/// Compute the velocity parameter `beta = sqrt(1 - (m1 + m2)^2 / s)`.
+/// Returns `None` if `s` is below the threshold.
+#[inline]
+pub fn threshold_beta<T: Float>(s: T, m1: T, m2: T) -> Option<T> {
+    let threshold = (m1 + m2) * (m1 + m2);
+    if s < threshold || s <= T::zero() {
+        return None;
+    }
+    Some(((s - threshold) / s).sqrt())
+}
+
+/// Create the momenta `[pa, pb, p1, p2]` of the scattering `pa pb -> p1 p2` in the center of
+/// mass frame, with massless incoming momenta along the z-axis, final-state masses `m1` and
+/// `m2` at the distance `beta` from threshold, and `p1` in the direction given by
+/// `cos_theta` and `phi`. The momentum of the final state is computed directly from `beta`,
+/// so that it does not suffer from cancellations close to threshold.
+pub fn threshold_momenta<T: Float + Field>(
+    m1: T,
+    m2: T,
+    beta: T,
+    cos_theta: T,
+    phi: T,
+) -> [LorentzVector<T>; 4] {
+    let two = T::one() + T::one();
+    let one_minus_beta2 = (T::one() - beta) * (T::one() + beta);
+    let mass_sum = m1 + m2;
+    let mass_diff = m1 - m2;
+    let sqrt_s = mass_sum / one_minus_beta2.sqrt();
+    // q = sqrt(lambda(s, m1^2, m2^2)) / (2 sqrt(s))
+    let q = beta / two * (mass_sum * mass_sum / one_minus_beta2 - mass_diff * mass_diff).sqrt();
+
+    let sin_theta = ((T::one() - cos_theta) * (T::one() + cos_theta))
+        .max(T::zero())
+        .sqrt();
+    let (sin_phi, cos_phi) = phi.sin_cos();
+    let n = LorentzVector::from_args(
+        T::zero(),
+        sin_theta * cos_phi,
+        sin_theta * sin_phi,
+        cos_theta,
+    ) * q;
+
+    let e = sqrt_s / two;
+    [
+        LorentzVector::from_args(e, T::zero(), T::zero(), e),
+        LorentzVector::from_args(e, T::zero(), T::zero(), -e),
+        LorentzVector::from_args(q.hypot(m1), n.x, n.y, n.z),
+        LorentzVector::from_args(q.hypot(m2), -n.x, -n.y, -n.z),
+    ]
+}
+
+/// The coefficients of the expansion of the Mandelstam variables of `threshold_momenta()`
+/// in powers of `beta`, where the coefficient at index `k` multiplies `beta^k`.
+#[derive(Debug, Clone)]
+pub struct ThresholdExpansion<T> {
+    pub s: Vec<T>,
+    pub t: Vec<T>,
+    pub u: Vec<T>,
+}
+
+impl<T: Float> ThresholdExpansion<T> {
+    /// Expand `s`, `t = (pa - p1)^2` and `u = (pa - p2)^2` up to and including `beta^order`,
+    /// for final-state masses `m1` and `m2` and the scattering angle `cos_theta` of
+    /// `threshold_momenta()`.
+    pub fn new(m1: T, m2: T, cos_theta: T, order: usize) -> ThresholdExpansion<T> {
+        let two = T::one() + T::one();
+        let four = two + two;
+        let mass_sum = m1 + m2;
+        let mass_diff = m1 - m2;
+        let n = order + 1;
+
+        // 1 / (1 - beta^2)
+        let geometric: Vec<T> = (0..n)
+            .map(|k| if k % 2 == 0 { T::one() } else { T::zero() })
+            .collect();
+
+        // sqrt(4 m1 m2 + (m1 - m2)^2 beta^2)
+        let a = four * m1 * m2;
+        let b = mass_diff * mass_diff;
+        let mut root = vec![T::zero(); n];
+        if a == T::zero() {
+            if n > 1 {
+                root[1] = mass_diff.abs();
+            }
+        } else {
+            let ratio = b / a;
+            let mut c = a.sqrt();
+            for k in (0..n).step_by(2) {
+                root[k] = c;
+                let j = T::from(k / 2).unwrap();
+                c = c * (T::one() / two - j) / (j + T::one()) * ratio;
+            }
+        }
+
+        // sqrt(s) q = sqrt(lambda) / 2 = (m1 + m2) beta / (2 (1 - beta^2)) * root
+        let product = multiply(&geometric, &root);
+        let mut sqrt_s_q = vec![T::zero(); n];
+        for k in 1..n {
+            sqrt_s_q[k] = mass_sum / two * product[k - 1];
+        }
+
+        let s: Vec<T> = geometric.iter().map(|&g| mass_sum * mass_sum * g).collect();
+        let mut t = vec![T::zero(); n];
+        let mut u = vec![T::zero(); n];
+        let constant = (m1 * m1 + m2 * m2) / two;
+        for k in 0..n {
+            let c = if k == 0 { constant } else { T::zero() };
+            t[k] = c - s[k] / two + cos_theta * sqrt_s_q[k];
+            u[k] = c - s[k] / two - cos_theta * sqrt_s_q[k];
+        }
+
+        ThresholdExpansion { s, t, u }
+    }
+
+    /// Evaluate the truncated expansions `(s, t, u)` at `beta`.
+    pub fn evaluate(&self, beta: T) -> (T, T, T) {
+        let horner = |c: &[T]| c.iter().rev().fold(T::zero(), |acc, &x| acc * beta + x);
+        (horner(&self.s), horner(&self.t), horner(&self.u))
+    }
+}
+
+/// Multiply two power series, truncated to the length of `a`.
+fn multiply<T: Float>(a: &[T], b: &[T]) -> Vec<T> {
+    (0..a.len())
+        .map(|k| {
+            (0..=k)
+                .filter(|&i| k - i < b.len())
+                .fold(T::zero(), |acc, i| acc + a[i] * b[k - i])
+        })
+        .collect()
+}
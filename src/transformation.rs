@@ -0,0 +1,389 @@
+use crate::linalg;
+use crate::{Field, LorentzVector};
+use num::Float;
+use std::fmt;
+use std::fmt::Display;
+use std::ops::Mul;
+
+/// A linear transformation of Lorentz vectors, stored as a 4x4 matrix acting on the
+/// components `(t, x, y, z)`. Transformations are composed with `*`, where `(a * b)`
+/// applies `b` first, so that a chain of boosts and rotations can be built once and
+/// applied to every vector of an event.
+#[derive(Debug, Copy, Clone)]
+pub struct LorentzTransformation<T: Field> {
+    matrix: [[T; 4]; 4],
+}
+
+impl<T: Field> Default for LorentzTransformation<T> {
+    fn default() -> LorentzTransformation<T> {
+        LorentzTransformation::identity()
+    }
+}
+
+impl<T: Field> Display for LorentzTransformation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.matrix.iter().enumerate() {
+            write!(
+                f,
+                "{}[{}, {}, {}, {}]",
+                if i == 0 { "" } else { "\n" },
+                row[0],
+                row[1],
+                row[2],
+                row[3]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Field> LorentzTransformation<T> {
+    #[inline]
+    pub fn identity() -> LorentzTransformation<T> {
+        let mut matrix = [[T::zero(); 4]; 4];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            row[i] = T::one();
+        }
+        LorentzTransformation { matrix }
+    }
+
+    /// Create a transformation from a row-major matrix.
+    #[inline]
+    pub fn from_matrix(matrix: [[T; 4]; 4]) -> LorentzTransformation<T> {
+        LorentzTransformation { matrix }
+    }
+
+    /// Create the matrix of a linear map of vectors, by applying it to the unit vectors.
+    pub fn from_linear_map<F: Fn(&LorentzVector<T>) -> LorentzVector<T>>(
+        f: F,
+    ) -> LorentzTransformation<T> {
+        let (zero, one) = (T::zero(), T::one());
+        let c = [
+            f(&LorentzVector::from_args(one, zero, zero, zero)),
+            f(&LorentzVector::from_args(zero, one, zero, zero)),
+            f(&LorentzVector::from_args(zero, zero, one, zero)),
+            f(&LorentzVector::from_args(zero, zero, zero, one)),
+        ];
+        LorentzTransformation {
+            matrix: [
+                [c[0].t, c[1].t, c[2].t, c[3].t],
+                [c[0].x, c[1].x, c[2].x, c[3].x],
+                [c[0].y, c[1].y, c[2].y, c[3].y],
+                [c[0].z, c[1].z, c[2].z, c[3].z],
+            ],
+        }
+    }
+
+    /// The row-major matrix.
+    #[inline]
+    pub fn matrix(&self) -> &[[T; 4]; 4] {
+        &self.matrix
+    }
+
+    #[inline]
+    pub fn apply(&self, v: &LorentzVector<T>) -> LorentzVector<T> {
+        let m = &self.matrix;
+        let row = |i: usize| m[i][0] * v.t + m[i][1] * v.x + m[i][2] * v.y + m[i][3] * v.z;
+        LorentzVector::from_args(row(0), row(1), row(2), row(3))
+    }
+
+    /// Transform all vectors in place.
+    pub fn apply_slice(&self, momenta: &mut [LorentzVector<T>]) {
+        for vec in momenta {
+            *vec = self.apply(vec);
+        }
+    }
+
+    pub fn transpose(&self) -> LorentzTransformation<T> {
+        let mut matrix = self.matrix;
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = self.matrix[j][i];
+            }
+        }
+        LorentzTransformation { matrix }
+    }
+
+    /// Compute the inverse `g L^T g` of a Lorentz transformation `L`, where `g` is the
+    /// metric. This is only the inverse if the transformation preserves the metric.
+    pub fn inverse(&self) -> LorentzTransformation<T> {
+        let mut matrix = self.transpose().matrix;
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                if (i == 0) != (j == 0) {
+                    *c = -*c;
+                }
+            }
+        }
+        LorentzTransformation { matrix }
+    }
+}
+
+impl<T: Float + Field> LorentzTransformation<T> {
+    /// Check if the transformation preserves the metric `g`, i.e. if every entry of
+    /// `L^T g L - g` is at most `tolerance` in magnitude.
+    pub fn is_lorentz(&self, tolerance: T) -> bool {
+        let m = &self.matrix;
+        let metric = |k: usize| if k == 0 { T::one() } else { -T::one() };
+        (0..4).all(|i| {
+            (0..4).all(|j| {
+                let c: T = (0..4).map(|k| metric(k) * m[k][i] * m[k][j]).sum();
+                let expected = if i == j { metric(i) } else { T::zero() };
+                (c - expected).abs() <= tolerance
+            })
+        })
+    }
+
+    pub fn determinant(&self) -> T {
+        let a: Vec<T> = self
+            .matrix
+            .iter()
+            .flat_map(|row| row.iter().cloned())
+            .collect();
+        // the matrix always has 4 x 4 components
+        linalg::determinant(&a, 4).unwrap()
+    }
+
+    /// Check if the transformation preserves the orientation of space and the direction
+    /// of time, i.e. if the determinant and the `tt` component are positive. Boosts,
+    /// rotations and their compositions have this property; use `is_lorentz()` to check
+    /// that the transformation is a Lorentz transformation in the first place.
+    pub fn is_proper_orthochronous(&self) -> bool {
+        self.determinant() > T::zero() && self.matrix[0][0] > T::zero()
+    }
+
+    /// Create the matrix of the boost with velocity `beta`, with the convention of
+    /// `LorentzVector::boost()`. Applying the matrix to many momenta avoids recomputing
+    /// the Lorentz factor for every momentum.
+    pub fn pure_boost(beta: &LorentzVector<T>) -> LorentzTransformation<T> {
+        let (gamma, gamma2) = LorentzVector::boost_factors(beta.spatial_squared());
+        let b = [beta.x, beta.y, beta.z];
+        let mut matrix = [[T::zero(); 4]; 4];
+        matrix[0][0] = gamma;
+        for i in 0..3 {
+            matrix[0][i + 1] = gamma * b[i];
+            matrix[i + 1][0] = gamma * b[i];
+            for j in 0..3 {
+                matrix[i + 1][j + 1] = gamma2 * b[i] * b[j];
+            }
+            matrix[i + 1][i + 1] += T::one();
+        }
+        LorentzTransformation { matrix }
+    }
+
+    /// Create the boost along the z-axis by `rapidity`.
+    pub fn boost_z(rapidity: T) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| e.boost_z(rapidity))
+    }
+
+    /// Create the counter-clockwise rotation by `angle` around the x-axis.
+    pub fn rotation_x(angle: T) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| e.rotate_x(angle))
+    }
+
+    /// Create the counter-clockwise rotation by `angle` around the y-axis.
+    pub fn rotation_y(angle: T) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| e.rotate_y(angle))
+    }
+
+    /// Create the counter-clockwise rotation by `angle` around the z-axis.
+    pub fn rotation_z(angle: T) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| e.rotate_z(angle))
+    }
+
+    /// Create the rotation with Euler angles `alpha`, `beta` and `gamma` in the convention
+    /// `convention`. For the axes `a`, `b` and `c` of the convention, the rotation is
+    /// `R_a(alpha) R_b(beta) R_c(gamma)`, which rotates by `gamma` first. With the default
+    /// `EulerConvention::ZYZ`, this is the active rotation used in Wigner D-functions.
+    pub fn from_euler_angles(
+        alpha: T,
+        beta: T,
+        gamma: T,
+        convention: EulerConvention,
+    ) -> LorentzTransformation<T> {
+        type Rotation<T> = fn(T) -> LorentzTransformation<T>;
+        let (x, y, z): (Rotation<T>, Rotation<T>, Rotation<T>) = (
+            LorentzTransformation::rotation_x,
+            LorentzTransformation::rotation_y,
+            LorentzTransformation::rotation_z,
+        );
+        let (a, b, c) = match convention {
+            EulerConvention::ZYZ => (z, y, z),
+            EulerConvention::ZXZ => (z, x, z),
+            EulerConvention::XYZ => (x, y, z),
+            EulerConvention::ZYX => (z, y, x),
+        };
+        a(alpha) * b(beta) * c(gamma)
+    }
+
+    /// Create the counter-clockwise rotation by `angle` around the spatial part of `axis`.
+    pub fn rotation_axis_angle(axis: &LorentzVector<T>, angle: T) -> LorentzTransformation<T> {
+        LorentzTransformation::from_linear_map(|e| e.rotate_axis_angle(axis, angle))
+    }
+}
+
+/// The axes of the rotations for `LorentzTransformation::from_euler_angles()`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EulerConvention {
+    ZYZ,
+    ZXZ,
+    XYZ,
+    ZYX,
+}
+
+impl Default for EulerConvention {
+    fn default() -> EulerConvention {
+        EulerConvention::ZYZ
+    }
+}
+
+/// A pure boost that sends `p` into `q`, with the setup of `LorentzVector::boost_from_to()`
+/// computed once, such that it can be applied to all vectors of an event.
+/// For details, see appendix A.2.2 of Simone Lionetti's PhD thesis.
+#[derive(Debug, Copy, Clone)]
+pub struct BoostFromTo<T: Field> {
+    na: LorentzVector<T>,
+    nb: LorentzVector<T>,
+    coeff_plus: T,
+    coeff_minus: T,
+    coinciding: bool,
+}
+
+impl<T: Float + Field> BoostFromTo<T> {
+    /// Prepare the boost that sends `p` into `q`.
+    pub fn new(p: &LorentzVector<T>, q: &LorentzVector<T>) -> BoostFromTo<T> {
+        let eps = T::epsilon() + T::epsilon();
+        let p_abs = p.euclidean_distance();
+        let q_abs = q.euclidean_distance();
+
+        if (p - q).spatial_distance() < eps * eps {
+            instability!("coinciding source and target in boost_from_to");
+            return BoostFromTo {
+                na: LorentzVector::new(),
+                nb: LorentzVector::new(),
+                coeff_plus: T::zero(),
+                coeff_minus: T::zero(),
+                coinciding: true,
+            };
+        }
+
+        let mut n_vec = q - p;
+        n_vec = n_vec / n_vec.spatial_distance();
+
+        let na = LorentzVector::from_args(T::one(), n_vec.x, n_vec.y, n_vec.z);
+        let nb = LorentzVector::from_args(T::one(), -n_vec.x, -n_vec.y, -n_vec.z);
+
+        let p_plus = p.dot(&nb);
+        let p_minus = p.dot(&na);
+        let q_plus = q.dot(&nb);
+        let q_minus = q.dot(&na);
+        let ratioa;
+        let ratiob;
+        if p_minus / p_abs < eps && q_minus / q_abs < eps {
+            instability!(
+                p_minus = ?p_minus,
+                q_minus = ?q_minus,
+                "vanishing minus components in boost_from_to"
+            );
+            if p_plus / p_abs < eps && q_plus / q_abs < eps {
+                ratioa = T::one();
+                ratiob = T::one();
+            } else {
+                ratiob = q_plus / p_plus;
+                ratioa = T::one() / ratiob;
+            }
+        } else {
+            if p_plus / p_abs < eps && q_plus / q_abs < eps {
+                instability!(
+                    p_plus = ?p_plus,
+                    q_plus = ?q_plus,
+                    "vanishing plus components in boost_from_to"
+                );
+                ratioa = q_minus / p_minus;
+                ratiob = T::one() / ratioa;
+            } else {
+                ratioa = q_minus / p_minus;
+                ratiob = q_plus / p_plus;
+            }
+        }
+
+        let two = T::one() + T::one();
+        let boost = BoostFromTo {
+            na,
+            nb,
+            coeff_plus: (ratiob - T::one()) / two,
+            coeff_minus: (ratioa - T::one()) / two,
+            coinciding: false,
+        };
+
+        // a boost can only map p into q if their squares agree
+        #[cfg(feature = "consistency_checks")]
+        {
+            if (p.square() - q.square()).abs() <= T::epsilon().sqrt() * p_abs * q_abs {
+                consistency_check!(
+                    (boost.apply(p) - q).euclidean_distance(),
+                    q_abs,
+                    "boost_from_to does not map p into q"
+                );
+            }
+        }
+
+        #[cfg(feature = "tracing_support")]
+        {
+            if (p.square() - q.square()).abs() > T::epsilon().sqrt() * p_abs * q_abs {
+                instability!(
+                    p_square = ?p.square(),
+                    q_square = ?q.square(),
+                    "boost_from_to between vectors with different squares"
+                );
+            }
+        }
+
+        boost
+    }
+
+    /// Boost `v`. If the source and target coincide, the zero vector is returned, as
+    /// for `LorentzVector::boost_from_to()`.
+    #[inline]
+    pub fn apply(&self, v: &LorentzVector<T>) -> LorentzVector<T> {
+        if self.coinciding {
+            return LorentzVector::new();
+        }
+
+        let plus = v.dot(&self.nb);
+        let minus = v.dot(&self.na);
+        v + self.na * (self.coeff_plus * plus) + self.nb * (self.coeff_minus * minus)
+    }
+}
+
+impl<T: Field> Mul<LorentzTransformation<T>> for LorentzTransformation<T> {
+    type Output = LorentzTransformation<T>;
+
+    fn mul(self, other: LorentzTransformation<T>) -> LorentzTransformation<T> {
+        let mut matrix = [[T::zero(); 4]; 4];
+        for (i, row) in matrix.iter_mut().enumerate() {
+            for (j, c) in row.iter_mut().enumerate() {
+                *c = (0..4).map(|k| self.matrix[i][k] * other.matrix[k][j]).sum();
+            }
+        }
+        LorentzTransformation { matrix }
+    }
+}
+
+impl<T: Field> Mul<&LorentzVector<T>> for &LorentzTransformation<T> {
+    type Output = LorentzVector<T>;
+
+    #[inline]
+    fn mul(self, other: &LorentzVector<T>) -> LorentzVector<T> {
+        self.apply(other)
+    }
+}
+
+impl<T: Field> Mul<LorentzVector<T>> for LorentzTransformation<T> {
+    type Output = LorentzVector<T>;
+
+    #[inline]
+    fn mul(self, other: LorentzVector<T>) -> LorentzVector<T> {
+        self.apply(&other)
+    }
+}
@@ -0,0 +1,228 @@
+use crate::{Field, LorentzVector};
+use num::Float;
+use std::fmt;
+use std::fmt::Display;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// A vector in the plane transverse to the beam axis.
+#[derive(Debug, Copy, Clone)]
+pub struct TransverseVector<T: Field> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Field> Default for TransverseVector<T> {
+    fn default() -> TransverseVector<T> {
+        TransverseVector {
+            x: T::default(),
+            y: T::default(),
+        }
+    }
+}
+
+impl<T: Field> Display for TransverseVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(x:{}, y:{})", self.x, self.y)
+    }
+}
+
+impl<T: Field> TransverseVector<T> {
+    #[inline]
+    pub fn new() -> TransverseVector<T> {
+        TransverseVector::default()
+    }
+
+    #[inline]
+    pub fn from_args(x: T, y: T) -> TransverseVector<T> {
+        TransverseVector { x, y }
+    }
+
+    #[inline]
+    pub fn square(&self) -> T {
+        self.x * self.x + self.y * self.y
+    }
+
+    #[inline]
+    pub fn dot(&self, other: &TransverseVector<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Compute the z-component of the cross product.
+    #[inline]
+    pub fn cross(&self, other: &TransverseVector<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Rotate by 90 degrees counter-clockwise.
+    #[inline]
+    pub fn perpendicular(&self) -> TransverseVector<T> {
+        TransverseVector {
+            x: -self.y,
+            y: self.x,
+        }
+    }
+
+    /// Embed in a `LorentzVector` with vanishing energy and longitudinal momentum.
+    #[inline]
+    pub fn to_lorentz_vector(&self) -> LorentzVector<T> {
+        LorentzVector::from_args(T::zero(), self.x, self.y, T::zero())
+    }
+}
+
+impl<T: Float + Field> TransverseVector<T> {
+    /// Compute the length, i.e. the transverse momentum.
+    #[inline]
+    pub fn pt(&self) -> T {
+        self.x.hypot(self.y)
+    }
+
+    /// Compute the azimuthal angle.
+    #[inline]
+    pub fn phi(&self) -> T {
+        self.y.atan2(self.x)
+    }
+
+    /// Compute the angle between two transverse vectors, in `[0, pi]`.
+    #[inline]
+    pub fn angle(&self, other: &TransverseVector<T>) -> T {
+        self.cross(other).atan2(self.dot(other)).abs()
+    }
+}
+
+impl<T: Field> LorentzVector<T> {
+    /// Get the transverse components.
+    #[inline]
+    pub fn transverse(&self) -> TransverseVector<T> {
+        TransverseVector {
+            x: self.x,
+            y: self.y,
+        }
+    }
+}
+
+impl<T: Field> From<LorentzVector<T>> for TransverseVector<T> {
+    #[inline]
+    fn from(v: LorentzVector<T>) -> TransverseVector<T> {
+        v.transverse()
+    }
+}
+
+impl<T: Field> From<&LorentzVector<T>> for TransverseVector<T> {
+    #[inline]
+    fn from(v: &LorentzVector<T>) -> TransverseVector<T> {
+        v.transverse()
+    }
+}
+
+impl<T: Field> Neg for TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn neg(self) -> TransverseVector<T> {
+        TransverseVector {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<'a, T: Field> Add<&'a TransverseVector<T>> for &'a TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn add(self, other: &'a TransverseVector<T>) -> TransverseVector<T> {
+        TransverseVector {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T: Field> Add<TransverseVector<T>> for TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn add(self, other: TransverseVector<T>) -> TransverseVector<T> {
+        &self + &other
+    }
+}
+
+impl<'a, T: Field> Sub<&'a TransverseVector<T>> for &'a TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn sub(self, other: &'a TransverseVector<T>) -> TransverseVector<T> {
+        TransverseVector {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: Field> Sub<TransverseVector<T>> for TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn sub(self, other: TransverseVector<T>) -> TransverseVector<T> {
+        &self - &other
+    }
+}
+
+impl<T: Field> AddAssign<TransverseVector<T>> for TransverseVector<T> {
+    #[inline]
+    fn add_assign(&mut self, other: TransverseVector<T>) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: Field> SubAssign<TransverseVector<T>> for TransverseVector<T> {
+    #[inline]
+    fn sub_assign(&mut self, other: TransverseVector<T>) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Field> Mul<T> for TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn mul(self, other: T) -> TransverseVector<T> {
+        TransverseVector {
+            x: self.x * other,
+            y: self.y * other,
+        }
+    }
+}
+
+impl<T: Field> MulAssign<T> for TransverseVector<T> {
+    #[inline]
+    fn mul_assign(&mut self, other: T) {
+        self.x *= other;
+        self.y *= other;
+    }
+}
+
+impl<T: Field> Div<T> for TransverseVector<T> {
+    type Output = TransverseVector<T>;
+
+    #[inline]
+    fn div(self, other: T) -> TransverseVector<T> {
+        let o = other.inv();
+        self * o
+    }
+}
+
+impl<T: Field> Sum<TransverseVector<T>> for TransverseVector<T> {
+    fn sum<I: Iterator<Item = TransverseVector<T>>>(iter: I) -> TransverseVector<T> {
+        iter.fold(TransverseVector::new(), |acc, v| acc + v)
+    }
+}
+
+impl<'a, T: Field> Sum<&'a TransverseVector<T>> for TransverseVector<T> {
+    fn sum<I: Iterator<Item = &'a TransverseVector<T>>>(iter: I) -> TransverseVector<T> {
+        iter.fold(TransverseVector::new(), |acc, v| acc + *v)
+    }
+}